@@ -1,27 +1,152 @@
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::{env, fs, io};
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
-    println!("cargo:rerun-if-changed=openvr/headers/openvr_api.json");
 
-    let json: Json =
-        serde_json::from_reader(fs::File::open("openvr/headers/openvr_api.json").unwrap()).unwrap();
+    let json = load_api_json();
 
     let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR not found");
     let dest_path = Path::new(&out_dir).join("generated.rs");
     let mut out = fs::File::create(dest_path).unwrap();
+    let dest_path = Path::new(&out_dir).join("exhaustive.rs");
+    let mut exhaustive_out = fs::File::create(dest_path).unwrap();
+    let dest_path = Path::new(&out_dir).join("types.rs");
+    let mut types_out = fs::File::create(dest_path).unwrap();
+    let dest_path = Path::new(&out_dir).join("consts.rs");
+    let mut consts_out = fs::File::create(dest_path).unwrap();
+    let dest_path = Path::new(&out_dir).join("low_level.rs");
+    let mut low_level_out = fs::File::create(dest_path).unwrap();
+    let dest_path = Path::new(&out_dir).join("properties.rs");
+    let mut properties_out = fs::File::create(dest_path).unwrap();
 
     for enum_info in &json.enums {
-        generate_enum(enum_info, &mut out).unwrap();
+        generate_enum(enum_info, &mut out, &mut exhaustive_out).unwrap();
+    }
+    let property_enum = json
+        .enums
+        .iter()
+        .find(|x| x.name == "vr::ETrackedDeviceProperty")
+        .expect("ETrackedDeviceProperty not found");
+    generate_properties(property_enum, &mut properties_out).unwrap();
+
+    let types = TypeTable::new(&json.typedefs);
+    generate_handles(&types, &mut types_out).unwrap();
+    generate_consts(&types, &json.consts, &mut consts_out).unwrap();
+    let mut structs = HashMap::new();
+    for struct_info in &json.structs {
+        generate_struct(&types, &mut structs, struct_info, &mut types_out).unwrap();
     }
 
+    let methods = MethodContext::new(&types, &json);
+    methods.generate(&json.methods, &mut low_level_out).unwrap();
+
     out.flush().unwrap();
+    exhaustive_out.flush().unwrap();
+    types_out.flush().unwrap();
+    consts_out.flush().unwrap();
+    low_level_out.flush().unwrap();
+    properties_out.flush().unwrap();
+}
+
+/// The environment variable to override path to `openvr_api.json`
+const API_JSON_ENV: &str = "OPENVR_API_JSON";
+
+/// `openvr_api.json` vendored from the OpenVR SDK `openvr_sys` is built with
+const VENDORED_API_JSON: &str = "headers/openvr_api.json";
+
+/// The interface versions of OpenVR SDK 2.5.1, which is bundled with `openvr_sys` 2.1.1.
+/// `openvr_api.json` must declare the same versions to generate bindings matching `openvr_sys`.
+const INTERFACE_VERSIONS: &[(&str, &str)] = &[
+    ("IVRSystem_Version", "IVRSystem_022"),
+    ("IVRExtendedDisplay_Version", "IVRExtendedDisplay_001"),
+    ("IVRTrackedCamera_Version", "IVRTrackedCamera_006"),
+    ("IVRApplications_Version", "IVRApplications_007"),
+    ("IVRChaperone_Version", "IVRChaperone_004"),
+    ("IVRChaperoneSetup_Version", "IVRChaperoneSetup_006"),
+    ("IVRCompositor_Version", "IVRCompositor_028"),
+    ("IVROverlay_Version", "IVROverlay_027"),
+    ("IVROverlayView_Version", "IVROverlayView_003"),
+    ("IVRHeadsetView_Version", "IVRHeadsetView_001"),
+    ("IVRRenderModels_Version", "IVRRenderModels_006"),
+    ("IVRNotifications_Version", "IVRNotifications_002"),
+    ("IVRSettings_Version", "IVRSettings_003"),
+    ("IVRScreenshots_Version", "IVRScreenshots_001"),
+    ("IVRResources_Version", "IVRResources_001"),
+    ("IVRDriverManager_Version", "IVRDriverManager_001"),
+    ("IVRInput_Version", "IVRInput_010"),
+    ("IVRIOBuffer_Version", "IVRIOBuffer_002"),
+    ("IVRSpatialAnchors_Version", "IVRSpatialAnchors_001"),
+    ("IVRDebug_Version", "IVRDebug_001"),
+    ("IVRProperties_Version", "IVRProperties_001"),
+    ("IVRPaths_Version", "IVRPaths_001"),
+];
+
+/// Loads `openvr_api.json` from `OPENVR_API_JSON` or the vendored one, and checks its version.
+fn load_api_json() -> Json {
+    println!("cargo:rerun-if-env-changed={}", API_JSON_ENV);
+    let path = match env::var_os(API_JSON_ENV) {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(VENDORED_API_JSON),
+    };
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let file = fs::File::open(&path).unwrap_or_else(|e| {
+        build_error(format_args!(
+            "failed to open openvr_api.json at {}: {}\n\
+            set {} to the path of openvr_api.json of the OpenVR SDK openvr_sys is built with",
+            path.display(),
+            e,
+            API_JSON_ENV,
+        ))
+    });
+    let json: Json = serde_json::from_reader(io::BufReader::new(file)).unwrap_or_else(|e| {
+        build_error(format_args!(
+            "failed to parse openvr_api.json at {}: {}",
+            path.display(),
+            e
+        ))
+    });
+
+    let mismatches = INTERFACE_VERSIONS
+        .iter()
+        .filter_map(|&(name, expected)| {
+            let actual = json.consts.iter().find(|x| x.name == name);
+            match actual.and_then(|x| x.value.as_deref()) {
+                Some(actual) if actual == expected => None,
+                actual => Some(format!(
+                    "    {}: expected {}, found {}",
+                    name,
+                    expected,
+                    actual.unwrap_or("nothing")
+                )),
+            }
+        })
+        .collect::<Vec<_>>();
+    if !mismatches.is_empty() {
+        build_error(format_args!(
+            "openvr_api.json at {} does not match openvr_sys 2.1.1 (OpenVR SDK 2.5.1):\n{}",
+            path.display(),
+            mismatches.join("\n")
+        ))
+    }
+
+    json
+}
+
+fn build_error(message: std::fmt::Arguments) -> ! {
+    eprintln!("error: {}", message);
+    std::process::exit(1)
 }
 
-fn generate_enum(enum_info: &EnumInfo, out: &mut impl Write) -> io::Result<()> {
+fn generate_enum(
+    enum_info: &EnumInfo,
+    out: &mut impl Write,
+    exhaustive_out: &mut impl Write,
+) -> io::Result<()> {
     let enum_kind = enum_kind(enum_info);
     match enum_kind {
         EnumKind::Unsigned => writeln!(out, "unsigned_enum! {{")?,
@@ -30,31 +155,11 @@ fn generate_enum(enum_info: &EnumInfo, out: &mut impl Write) -> io::Result<()> {
         EnumKind::SignedBitflags => writeln!(out, "signed_bits_enum! {{")?,
     }
 
-    let enum_name = enum_info
-        .name
-        .split_once("::")
-        .unwrap_or(("", &enum_info.name))
-        .1;
+    let enum_name = strip_namespace(&enum_info.name);
     let common_len = find_common_prefix_len(enum_info);
+    let enum_name_rs = enum_name_rs(enum_name);
 
-    writeln!(
-        out,
-        "    {};",
-        enum_name
-            .trim_start_matches("E")
-            .trim_start_matches("VR")
-            .split('_')
-            .collect::<String>()
-    )?;
-
-    fn value_name_rs(common_len: usize, mut value_cpp_name: &str) -> String {
-        value_cpp_name = value_cpp_name.split_at(common_len).1;
-        let mut value_name = value_cpp_name.split('_').collect::<String>();
-        if value_name.as_bytes()[0] == b'e' {
-            unsafe { value_name.as_bytes_mut()[0] = b'E' }
-        }
-        value_name
-    }
+    writeln!(out, "    {} as ::openvr_sys::{};", enum_name_rs, enum_name)?;
 
     fn write_value(
         enum_name: &str,
@@ -65,29 +170,168 @@ fn generate_enum(enum_info: &EnumInfo, out: &mut impl Write) -> io::Result<()> {
         if value_cpp_name.starts_with(enum_name) {
             writeln!(
                 out,
-                "    {} = ::openvr_sys::{},",
-                value_rs_name, value_cpp_name
+                "    {} = ::openvr_sys::{} => {:?},",
+                value_rs_name, value_cpp_name, value_cpp_name
             )
         } else {
             writeln!(
                 out,
-                "    {} = ::openvr_sys::{}_{},",
-                value_rs_name, enum_name, value_cpp_name
+                "    {} = ::openvr_sys::{}_{} => {:?},",
+                value_rs_name, enum_name, value_cpp_name, value_cpp_name
             )
         }
     }
 
+    // values with same value as previous one are aliases
+    let mut seen_values = HashSet::new();
+    let mut values = vec![];
+    let mut aliases = vec![];
+
     for value_info in &enum_info.values {
         if is_max_entry(&value_info.name) {
             continue;
         }
 
+        if seen_values.insert(&value_info.value) {
+            values.push(value_info);
+        } else {
+            aliases.push(value_info);
+        }
+    }
+
+    for value_info in &values {
         let value_name = value_name_rs(common_len, &value_info.name);
 
         write_value(enum_name, &value_name, &value_info.name, out)?;
     }
 
-    writeln!(out, "}}")
+    if !aliases.is_empty() {
+        writeln!(out, "    ; alias")?;
+        for value_info in &aliases {
+            let value_name = value_name_rs(common_len, &value_info.name);
+
+            write_value(enum_name, &value_name, &value_info.name, out)?;
+        }
+    }
+
+    writeln!(out, "}}")?;
+
+    // exhaustive enum
+    writeln!(exhaustive_out, "exhaustive_enum! {{")?;
+    writeln!(
+        exhaustive_out,
+        "    {} as {};",
+        enum_name_rs,
+        enum_kind.raw_type()
+    )?;
+    for value_info in &values {
+        let value_name = value_name_rs(common_len, &value_info.name);
+        // Unknown is used for unknown values
+        let variant_name = if value_name == "Unknown" {
+            "UnknownValue"
+        } else {
+            &value_name
+        };
+        writeln!(exhaustive_out, "    {} = {},", variant_name, value_name)?;
+    }
+    writeln!(exhaustive_out, "}}")
+}
+
+/// value type of `Prop_*` from its type suffix
+const PROPERTY_TYPES: &[(&str, &str)] = &[
+    ("_Bool", "bool"),
+    ("_Float", "f32"),
+    ("_Int32", "i32"),
+    ("_Uint64", "u64"),
+    ("_String", "String"),
+    ("_Matrix34", "crate::HmdMatrix34_t"),
+    ("_Vector3", "crate::HmdVector3_t"),
+    ("_Float_Array", "Vec<f32>"),
+    ("_Int32_Array", "Vec<i32>"),
+    ("_Matrix34_Array", "Vec<crate::HmdMatrix34_t>"),
+    ("_Vector4_Array", "Vec<crate::HmdVector4_t>"),
+];
+
+/// generates typed `Property` keys for `ETrackedDeviceProperty` values with known type suffix
+fn generate_properties(enum_info: &EnumInfo, out: &mut impl Write) -> io::Result<()> {
+    let enum_name = enum_name_rs(strip_namespace(&enum_info.name));
+    let common_len = find_common_prefix_len(enum_info);
+
+    // Prop_SerialNumber_String -> (SERIAL_NUMBER, String)
+    let properties = enum_info
+        .values
+        .iter()
+        .filter_map(|value_info| {
+            let name = value_info.name.strip_prefix("Prop_")?;
+            // the longest suffix wins: `_Float_Array` over `_Array`
+            let (suffix, ty) = PROPERTY_TYPES
+                .iter()
+                .filter(|(suffix, _)| name.ends_with(suffix))
+                .max_by_key(|(suffix, _)| suffix.len())?;
+            let name = to_snake_case(name.strip_suffix(suffix).unwrap()).to_ascii_uppercase();
+            Some((value_info, name, suffix, ty))
+        })
+        .collect::<Vec<_>>();
+
+    let mut name_count = HashMap::<&str, usize>::new();
+    for (_, name, _, _) in &properties {
+        *name_count.entry(name).or_default() += 1;
+    }
+
+    let mut all = vec![];
+    for (value_info, name, suffix, ty) in &properties {
+        // same name with different types like `Prop_HardwareRevision_String` and `_Uint64`
+        let name = if name_count[name.as_str()] > 1 {
+            format!("{}{}", name, suffix.to_ascii_uppercase())
+        } else {
+            name.clone()
+        };
+        // _Float_Array -> FloatArray
+        all.push(format!(
+            "AnyProperty::{}({})",
+            suffix.replace('_', ""),
+            name
+        ));
+        writeln!(out, "/// `{}`", value_info.name)?;
+        writeln!(
+            out,
+            "pub const {}: Property<{}> = Property::new(crate::{}::{});",
+            name,
+            ty,
+            enum_name,
+            value_name_rs(common_len, &value_info.name),
+        )?;
+    }
+
+    writeln!(out, "/// All keys above")?;
+    writeln!(out, "pub const ALL: &[AnyProperty] = &[")?;
+    for property in all {
+        writeln!(out, "    {},", property)?;
+    }
+    writeln!(out, "];")?;
+
+    Ok(())
+}
+
+fn value_name_rs(common_len: usize, mut value_cpp_name: &str) -> String {
+    value_cpp_name = value_cpp_name.split_at(common_len).1;
+    let mut value_name = value_cpp_name.split('_').collect::<String>();
+    if value_name.as_bytes()[0] == b'e' {
+        unsafe { value_name.as_bytes_mut()[0] = b'E' }
+    }
+    value_name
+}
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("vr::").unwrap_or(name)
+}
+
+fn enum_name_rs(enum_name: &str) -> String {
+    enum_name
+        .trim_start_matches('E')
+        .trim_start_matches("VR")
+        .split('_')
+        .collect::<String>()
 }
 
 enum EnumKind {
@@ -97,6 +341,15 @@ enum EnumKind {
     SignedBitflags,
 }
 
+impl EnumKind {
+    fn raw_type(&self) -> &'static str {
+        match self {
+            EnumKind::Unsigned | EnumKind::UnsignedBitflags => "u32",
+            EnumKind::Signed | EnumKind::SignedBitflags => "i32",
+        }
+    }
+}
+
 fn enum_kind(enum_info: &EnumInfo) -> EnumKind {
     let mut existing = 0;
     let mut bits = true;
@@ -119,9 +372,6 @@ fn enum_kind(enum_info: &EnumInfo) -> EnumKind {
         }
     }
 
-    let prefer_signed = env::var("CARGO_CFG_TARGET_OS").unwrap() == "windows";
-    signed |= prefer_signed;
-
     if non_zero_cnt > 2 && bits {
         if signed {
             EnumKind::SignedBitflags
@@ -139,7 +389,7 @@ fn enum_kind(enum_info: &EnumInfo) -> EnumKind {
 
 fn find_common_prefix_len(enum_info: &EnumInfo) -> usize {
     let mut values = enum_info.values.iter();
-    let mut common_prefix: &[u8] = &values.next().unwrap().name.as_bytes();
+    let mut common_prefix: &[u8] = values.next().unwrap().name.as_bytes();
 
     for value in values {
         if is_max_entry(&value.name) {
@@ -153,7 +403,7 @@ fn find_common_prefix_len(enum_info: &EnumInfo) -> usize {
         common_prefix = &common_prefix[..prefix_count];
     }
 
-    if common_prefix.len() == 0 {
+    if common_prefix.is_empty() {
         panic!("err enum: {}", enum_info.name)
     }
 
@@ -172,9 +422,1012 @@ fn is_max_entry(name: &str) -> bool {
             .all(|&x| x.is_ascii_uppercase() || x == b'_')
 }
 
+/// Integer typedefs which are actually bit sets of some enum
+const ENUM_TYPEDEFS: &[(&str, &str)] = &[("VRComponentProperties", "EVRComponentProperty")];
+
+/// Resolves typedefs in openvr_api.json
+struct TypeTable {
+    typedefs: HashMap<String, String>,
+    /// typedef name of struct, e.g. `VRControllerState001_t` -> `VRControllerState_t`
+    struct_typedefs: HashMap<String, String>,
+    handles: Vec<String>,
+}
+
+impl TypeTable {
+    fn new(typedefs: &[TypedefInfo]) -> Self {
+        let mut table = TypeTable {
+            typedefs: HashMap::new(),
+            struct_typedefs: HashMap::new(),
+            handles: vec![],
+        };
+
+        for typedef in typedefs {
+            let name = strip_namespace(&typedef.name);
+            let ty = strip_namespace(typedef.ty.trim());
+            if let Some(struct_name) = ty.strip_prefix("struct vr::") {
+                table
+                    .struct_typedefs
+                    .insert(struct_name.to_owned(), name.to_owned());
+            }
+            if table
+                .typedefs
+                .insert(name.to_owned(), ty.to_owned())
+                .is_none()
+            {
+                table.handles.push(name.to_owned());
+            }
+        }
+
+        // vrshared_* and gl* are not handles but just a primitive types
+        let handles = std::mem::take(&mut table.handles)
+            .into_iter()
+            .filter(|name| !name.starts_with("vrshared_") && !name.starts_with("gl"))
+            .filter(|name| ENUM_TYPEDEFS.iter().all(|&(typedef, _)| typedef != name))
+            .filter(|name| table.primitive(name).is_some())
+            .collect();
+        table.handles = handles;
+
+        table
+    }
+
+    /// resolves typedef name to rust primitive type
+    fn primitive(&self, name: &str) -> Option<&'static str> {
+        let name = strip_namespace(name);
+        primitive_type(name).or_else(|| self.primitive(self.typedefs.get(name)?))
+    }
+
+    fn is_handle(&self, name: &str) -> bool {
+        let name = strip_namespace(name);
+        self.handles.iter().any(|x| x == name)
+    }
+
+    /// resolves typedef of struct like `VRControllerState_t` to the struct name
+    fn typedef_struct<'a>(&'a self, name: &'a str) -> &'a str {
+        let name = strip_namespace(name);
+        match self.typedefs.get(name) {
+            Some(ty) => ty.strip_prefix("struct vr::").unwrap_or(name),
+            None => name,
+        }
+    }
+
+    fn struct_ffi_name<'a>(&'a self, name: &'a str) -> &'a str {
+        let name = strip_namespace(name);
+        self.struct_typedefs
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or(name)
+    }
+}
+
+fn primitive_type(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "uint8_t" => "u8",
+        "uint16_t" => "u16",
+        "uint32_t" => "u32",
+        "uint64_t" => "u64",
+        "int32_t" | "int" => "i32",
+        "unsigned short" => "u16",
+        "int64_t" => "i64",
+        "float" => "f32",
+        "double" => "f64",
+        "_Bool" | "bool" => "bool",
+        _ => return None,
+    })
+}
+
+fn handle_name_rs(name: &str) -> &str {
+    let name = strip_namespace(name);
+    name.strip_suffix("_t").unwrap_or(name)
+}
+
+fn struct_name_rs(ffi_name: &str) -> String {
+    ffi_name
+        .strip_suffix("_t")
+        .unwrap_or(ffi_name)
+        .split('_')
+        .collect()
+}
+
+/// converts `CamelCase` names to `snake_case`. acronyms like `CPUTime` is converted to `cpu_time`
+/// and digits are kept in the word, like `D3D9Adapter` to `d3d9_adapter` and `Axis0Type` to `axis0_type`.
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let is_lower = |i: usize| chars.get(i).is_some_and(|c| c.is_ascii_lowercase());
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && i != 0 && !result.ends_with('_') {
+            let prev = chars[i - 1];
+            if prev.is_ascii_lowercase()
+                || (prev.is_ascii_digit() && is_lower(i + 1))
+                || (prev.is_ascii_uppercase() && is_lower(i + 1) && is_lower(i + 2))
+            {
+                result.push('_');
+            }
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+/// remove hungarian notation prefix like `m_`, `un`, `pch` or `fl`
+fn strip_hungarian(name: &str) -> &str {
+    let name = name.strip_prefix("m_").unwrap_or(name);
+    const PREFIXES: &[&str] = &[
+        "pvec", "pmat", "pch", "pul", "pun", "ppv", "rch", "pe", "pb", "pn", "pf", "pv", "un",
+        "ul", "fl", "rf", "p", "n", "f", "b", "e", "v", "m", "u", "r", "h",
+    ];
+    for prefix in PREFIXES {
+        if let Some(stripped) = name.strip_prefix(prefix) {
+            if stripped.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return stripped;
+            }
+        }
+    }
+    name
+}
+
+fn escape_keyword(name: &str) -> String {
+    match name {
+        "type" | "match" | "ref" | "move" | "fn" | "mod" | "use" | "impl" | "loop" | "in"
+        | "self" | "box" | "where" | "override" => {
+            format!("{}_", name)
+        }
+        _ => name.to_owned(),
+    }
+}
+
+fn generate_handles(types: &TypeTable, out: &mut impl Write) -> io::Result<()> {
+    for handle in &types.handles {
+        writeln!(
+            out,
+            "handle_type!({}({}));",
+            handle_name_rs(handle),
+            types.primitive(handle).unwrap(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Constants which are handles but declared as plain integers in json
+fn const_handle(name: &str) -> Option<&'static str> {
+    name.starts_with("k_unTrackedDeviceIndex")
+        .then_some("TrackedDeviceIndex_t")
+}
+
+fn generate_consts(
+    types: &TypeTable,
+    consts: &[ConstInfo],
+    out: &mut impl Write,
+) -> io::Result<()> {
+    for const_info in consts {
+        let const_type = const_handle(&const_info.name)
+            .unwrap_or_else(|| const_info.ty.trim_start_matches("const "));
+        // k_unMaxTrackedDeviceCount -> MaxTrackedDeviceCount, k_pch_SteamVR_Section -> SteamVR_Section
+        let name = match const_info.name.strip_prefix("k_") {
+            Some(name) => name
+                .trim_start_matches(|c: char| c.is_ascii_lowercase())
+                .trim_start_matches('_'),
+            None => &const_info.name,
+        };
+        let name = to_snake_case(name).to_ascii_uppercase();
+
+        writeln!(out, "/// `{}`", const_info.name)?;
+        if const_type.starts_with("char *") {
+            writeln!(
+                out,
+                "pub const {}: &::std::ffi::CStr = unsafe {{ ::std::ffi::CStr::from_bytes_with_nul_unchecked(::openvr_sys::{}) }};",
+                name, const_info.name,
+            )?;
+        } else {
+            // C API may use platform dependent type like `unsigned long` so cast is required
+            let ty = types
+                .primitive(const_type)
+                .unwrap_or_else(|| panic!("unknown const type: {}", const_info.ty));
+            if types.is_handle(const_type) {
+                let handle = handle_name_rs(const_type);
+                writeln!(
+                    out,
+                    "pub const {}: crate::{} = crate::{}(::openvr_sys::{} as {});",
+                    name, handle, handle, const_info.name, ty,
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "pub const {}: {} = ::openvr_sys::{} as {};",
+                    name, ty, const_info.name, ty,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// How struct field is converted between the mirror and openvr_sys struct
+#[derive(Copy, Clone)]
+enum FieldConversion {
+    Copy,
+    Enum,
+    /// enums stored as integer typedef like `VRComponentProperties`
+    Raw,
+    Into,
+    MapInto,
+}
+
+impl FieldConversion {
+    fn name(self) -> &'static str {
+        match self {
+            FieldConversion::Copy => "copy",
+            FieldConversion::Enum => "enum",
+            FieldConversion::Raw => "raw",
+            FieldConversion::Into => "into",
+            FieldConversion::MapInto => "map_into",
+        }
+    }
+}
+
+/// Returns rust type and conversion of the field type, or None if the type cannot be mirrored
+fn field_type(
+    types: &TypeTable,
+    structs: &HashMap<String, String>,
+    ty: &str,
+) -> Option<(String, FieldConversion)> {
+    let ty = ty.trim();
+    if ty.contains('*') {
+        return None;
+    }
+
+    if let Some((base, dims)) = ty.split_once('[') {
+        if base.trim() == "char" {
+            return None;
+        }
+        let (mut rust_ty, conversion) = field_type(types, structs, base)?;
+        let conversion = match conversion {
+            FieldConversion::Copy => FieldConversion::Copy,
+            FieldConversion::Into if !dims.contains('[') => FieldConversion::MapInto,
+            _ => return None,
+        };
+        for dim in dims.rsplit('[') {
+            rust_ty = format!("[{}; {}]", rust_ty, dim.trim().trim_end_matches(']'));
+        }
+        return Some((rust_ty, conversion));
+    }
+
+    if let Some(enum_name) = ty.strip_prefix("enum ") {
+        return Some((
+            enum_name_rs(strip_namespace(enum_name)),
+            FieldConversion::Enum,
+        ));
+    }
+
+    let name = strip_namespace(ty.strip_prefix("struct ").unwrap_or(ty));
+    if let Some(rust_name) = structs.get(name) {
+        Some((rust_name.clone(), FieldConversion::Into))
+    } else if let Some(&(_, enum_name)) = ENUM_TYPEDEFS.iter().find(|&&(x, _)| x == name) {
+        Some((enum_name_rs(enum_name), FieldConversion::Raw))
+    } else if types.is_handle(name) {
+        Some((handle_name_rs(name).to_owned(), FieldConversion::Into))
+    } else {
+        Some((types.primitive(name)?.to_owned(), FieldConversion::Copy))
+    }
+}
+
+/// Handles which are declared as plain integers in json, like `uint64_t overlayHandle`
+fn field_handle(struct_name: &str, field_name: &str) -> Option<&'static str> {
+    Some(match (struct_name, field_name) {
+        (_, "overlayHandle") => "VROverlayHandle_t",
+        (_, "containerHandle" | "ulApplicationPropertyContainer") => "PropertyContainerHandle_t",
+        (_, "notificationId") => "VRNotificationId",
+        ("VREvent_Screenshot_t", "handle") => "ScreenshotHandle_t",
+        (_, name) if name.starts_with("path") || name == "devicePath" => "PathHandle_t",
+        _ => return None,
+    })
+}
+
+/// Structs which inherits another struct in C++. C API flattens fields of base struct
+/// but json does not have them so we cannot mirror them.
+fn is_derived_struct(name: &str) -> bool {
+    name.starts_with("VRTextureWith")
+}
+
+fn generate_struct(
+    types: &TypeTable,
+    structs: &mut HashMap<String, String>,
+    struct_info: &StructInfo,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let name = strip_namespace(&struct_info.name);
+    if name.contains('(') || is_derived_struct(name) {
+        return Ok(());
+    }
+    let ffi_name = types.struct_ffi_name(name);
+    let name_rs = struct_name_rs(ffi_name);
+
+    let mut fields = vec![];
+    for field in &struct_info.fields {
+        let ty = field_handle(name, &field.name).unwrap_or(&field.ty);
+        let Some((ty, conversion)) = field_type(types, structs, ty) else {
+            return Ok(());
+        };
+        fields.push((&field.name, ty, conversion));
+    }
+
+    let mut field_names = fields
+        .iter()
+        .map(|(name, _, _)| to_snake_case(strip_hungarian(name)))
+        .collect::<Vec<_>>();
+    if field_names.iter().collect::<HashSet<_>>().len() != field_names.len() {
+        // like uMin and vMin
+        field_names = fields
+            .iter()
+            .map(|(name, _, _)| to_snake_case(name))
+            .collect();
+    }
+
+    writeln!(out, "mirror_struct! {{")?;
+    writeln!(out, "    {} as ::openvr_sys::{} {{", name_rs, ffi_name)?;
+    for ((ffi_field, ty, conversion), field_name) in fields.iter().zip(&field_names) {
+        writeln!(
+            out,
+            "        {}: {} = {} {},",
+            escape_keyword(field_name),
+            ty,
+            escape_keyword(ffi_field),
+            conversion.name(),
+        )?;
+    }
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")?;
+
+    structs.insert(name.to_owned(), name_rs);
+    Ok(())
+}
+
+/// Information of enum used by method generation
+struct EnumMeta {
+    name_rs: String,
+    /// name of the success value if this is error enum
+    success: Option<String>,
+}
+
+impl EnumMeta {
+    fn new(enum_info: &EnumInfo) -> Self {
+        let name = strip_namespace(&enum_info.name);
+        let success = if name.ends_with("Error") {
+            let common_len = find_common_prefix_len(enum_info);
+            enum_info
+                .values
+                .iter()
+                .find(|x| x.value == "0")
+                .map(|x| value_name_rs(common_len, &x.name))
+        } else {
+            None
+        };
+        EnumMeta {
+            name_rs: enum_name_rs(name),
+            success,
+        }
+    }
+}
+
+enum TypeClass<'a> {
+    Void,
+    Char,
+    Primitive(&'static str),
+    Enum(&'a str, &'a EnumMeta),
+    Handle(String),
+    Struct(&'a str, bool),
+    Unknown,
+}
+
+/// The role of the parameter in low-level wrapper
+#[derive(Clone)]
+enum ParamRole {
+    /// passed as is (with conversion to ffi value)
+    Value,
+    /// `const char *` passed as `&CStr`
+    CStr,
+    /// pointer to the elements with count parameter
+    Slice { mutable: bool },
+    /// void or char buffer with size parameter
+    Bytes { mutable: bool },
+    /// length of the slice parameter
+    Len(usize),
+    /// pointer to the length of the slice parameter, which will be updated by OpenVR
+    InOutLen(usize),
+    /// size of struct
+    SizeOf(String),
+    /// const pointer to single struct
+    Ref,
+    /// out parameter returned as return value
+    Out,
+    /// pointer to the error enum
+    ErrorOut,
+    /// passed as raw pointer. the function will be unsafe
+    Raw,
+}
+
+/// count of `*`, base type, and whether the pointee is const
+fn pointer_info(ty: &str) -> (&str, usize, bool) {
+    let mut base = ty.trim();
+    let mut depth = 0;
+    while let Some(stripped) = base.strip_suffix('*') {
+        base = stripped.trim_end().trim_end_matches(" const").trim_end();
+        depth += 1;
+    }
+    let is_const = base.starts_with("const ");
+    (base, depth, is_const)
+}
+
+fn is_size_name(name: &str) -> bool {
+    name.contains("Size") || name.starts_with("uncb")
+}
+
+fn is_count_name(name: &str) -> bool {
+    name.contains("Count") || name.starts_with("unNum") || name.starts_with("nNum")
+}
+
+fn is_length_name(name: &str) -> bool {
+    is_size_name(name) || is_count_name(name) || name.contains("Len")
+}
+
+struct MethodContext<'a> {
+    types: &'a TypeTable,
+    enums: HashMap<String, EnumMeta>,
+    /// struct name to whether the struct has pointer
+    structs: HashMap<String, bool>,
+    /// structs with `m_nSize` field which should be initialized with size of struct
+    sized_structs: HashSet<String>,
+}
+
+impl<'a> MethodContext<'a> {
+    fn new(types: &'a TypeTable, json: &Json) -> Self {
+        let enums = json
+            .enums
+            .iter()
+            .map(|x| (strip_namespace(&x.name).to_owned(), EnumMeta::new(x)))
+            .collect();
+        let mut context = MethodContext {
+            types,
+            enums,
+            structs: HashMap::new(),
+            sized_structs: HashSet::new(),
+        };
+        for struct_info in &json.structs {
+            let name = strip_namespace(&struct_info.name);
+            let has_pointer = is_derived_struct(name)
+                || struct_info.fields.iter().any(|field| {
+                    let ty = context.resolve_pointer_typedef(&field.ty);
+                    let (base, depth, _) = pointer_info(&ty);
+                    let base = base.split('[').next().unwrap();
+                    depth != 0 || matches!(context.classify(base), TypeClass::Struct(_, true))
+                });
+            if struct_info.fields.first().map(|x| x.name.as_str()) == Some("m_nSize") {
+                context.sized_structs.insert(name.to_owned());
+            }
+            context.structs.insert(name.to_owned(), has_pointer);
+        }
+        context
+    }
+
+    /// resolves typedef of pointer like `glSharedTextureHandle_t`
+    fn resolve_pointer_typedef(&self, ty: &str) -> String {
+        let (base, depth, _) = pointer_info(ty);
+        let name = strip_namespace(base.trim_start_matches("const "));
+        match self.types.typedefs.get(name) {
+            Some(resolved) if resolved.contains('*') => {
+                format!("{}{}", resolved, " *".repeat(depth))
+            }
+            _ => ty.to_owned(),
+        }
+    }
+
+    fn classify(&self, base: &str) -> TypeClass<'_> {
+        let mut base = base.trim();
+        for prefix in ["const ", "struct ", "enum ", "class "] {
+            base = base.strip_prefix(prefix).unwrap_or(base);
+        }
+        let base = strip_namespace(base.trim());
+
+        if base == "void" {
+            TypeClass::Void
+        } else if base == "char" {
+            TypeClass::Char
+        } else if let Some(primitive) = primitive_type(base) {
+            TypeClass::Primitive(primitive)
+        } else if let Some((name, meta)) = self.enums.get_key_value(base) {
+            TypeClass::Enum(name, meta)
+        } else if self.types.is_handle(base) {
+            TypeClass::Handle(handle_name_rs(base).to_owned())
+        } else if let Some((name, &has_pointer)) =
+            self.structs.get_key_value(self.types.typedef_struct(base))
+        {
+            TypeClass::Struct(self.types.struct_ffi_name(name), has_pointer)
+        } else if let Some(primitive) = self.types.primitive(base) {
+            TypeClass::Primitive(primitive)
+        } else {
+            TypeClass::Unknown
+        }
+    }
+
+    /// rust type of the element of the pointer or slice
+    fn element_type(&self, class: &TypeClass) -> String {
+        match class {
+            TypeClass::Void | TypeClass::Unknown => "::std::os::raw::c_void".to_owned(),
+            TypeClass::Char => "::std::os::raw::c_char".to_owned(),
+            TypeClass::Primitive(primitive) => (*primitive).to_owned(),
+            TypeClass::Enum(ffi, _) => format!("::openvr_sys::{}", ffi),
+            TypeClass::Handle(handle) => format!("crate::{}", handle),
+            TypeClass::Struct(ffi, _) => format!("::openvr_sys::{}", ffi),
+        }
+    }
+
+    fn raw_pointer_type(&self, ty: &str) -> String {
+        let (base, depth, is_const) = pointer_info(ty);
+        let mut result = self.element_type(&self.classify(base));
+        for i in 0..depth {
+            if i == 0 && is_const {
+                result = format!("*const {}", result);
+            } else {
+                result = format!("*mut {}", result);
+            }
+        }
+        result
+    }
+
+    fn generate(&self, methods: &[MethodInfo], out: &mut impl Write) -> io::Result<()> {
+        let mut classes = Vec::<&str>::new();
+        for method in methods {
+            let class = strip_namespace(&method.class);
+            if !classes.contains(&class) {
+                classes.push(class);
+            }
+        }
+
+        for class in classes {
+            writeln!(
+                out,
+                "low_level_interface!({} as VR_{}_FnTable, {}_Version);",
+                class, class, class
+            )?;
+            writeln!(out)?;
+            writeln!(out, "impl<'a> {}<'a> {{", class)?;
+            for method in methods
+                .iter()
+                .filter(|x| strip_namespace(&x.class) == class)
+            {
+                self.generate_method(class, method, out)?;
+            }
+            writeln!(out, "}}")?;
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    fn param_roles(&self, params: &[ParamInfo]) -> Vec<ParamRole> {
+        let find = |name: &str| {
+            params
+                .iter()
+                .position(|x| x.name == name)
+                .unwrap_or_else(|| panic!("param {} not found", name))
+        };
+        let mut roles = vec![None; params.len()];
+
+        for (i, param) in params.iter().enumerate() {
+            if roles[i].is_some() {
+                continue;
+            }
+            let ty = self.resolve_pointer_typedef(&param.ty);
+            let (base, depth, is_const) = pointer_info(&ty);
+            let class = self.classify(base);
+            let next_is_length = params
+                .get(i + 1)
+                .is_some_and(|x| is_length_name(&x.name) && pointer_info(&x.ty).1 == 0);
+
+            let role = if depth == 0 {
+                if matches!(class, TypeClass::Unknown) {
+                    panic!("unknown type: {}", param.ty)
+                }
+                ParamRole::Value
+            } else if let Some(count) = &param.array_count {
+                roles[find(count)] = Some(ParamRole::Len(i));
+                ParamRole::Slice { mutable: !is_const }
+            } else if let Some(count) = &param.out_array_count {
+                roles[find(count)] = Some(ParamRole::InOutLen(i));
+                ParamRole::Slice { mutable: true }
+            } else if let Some(count) = &param.out_buffer_count {
+                roles[find(count)] = Some(ParamRole::Len(i));
+                ParamRole::Bytes { mutable: !is_const }
+            } else if depth != 1 {
+                ParamRole::Raw
+            } else if param.out_string.is_some()
+                || matches!(class, TypeClass::Void | TypeClass::Char) && next_is_length
+            {
+                roles[i + 1] = Some(ParamRole::Len(i));
+                ParamRole::Bytes { mutable: !is_const }
+            } else if matches!(class, TypeClass::Char) && is_const {
+                ParamRole::CStr
+            } else if matches!(
+                class,
+                TypeClass::Void | TypeClass::Char | TypeClass::Unknown
+            ) {
+                ParamRole::Raw
+            } else {
+                // find size and count parameters for the pointer
+                let mut is_slice = false;
+                for j in i + 1..params.len().min(i + 3) {
+                    let name = &params[j].name;
+                    let by_value = pointer_info(&params[j].ty).1 == 0;
+                    if roles[j].is_some() || !by_value {
+                        break;
+                    } else if is_count_name(name) {
+                        roles[j] = Some(ParamRole::Len(i));
+                        is_slice = true;
+                    } else if let (TypeClass::Struct(ffi, _), true) = (&class, is_size_name(name)) {
+                        roles[j] = Some(ParamRole::SizeOf(ffi.to_string()));
+                    } else {
+                        break;
+                    }
+                }
+
+                match class {
+                    _ if is_slice => ParamRole::Slice { mutable: !is_const },
+                    TypeClass::Struct(_, _) if is_const => ParamRole::Ref,
+                    TypeClass::Struct(_, true) => ParamRole::Raw,
+                    _ if is_const => ParamRole::Raw,
+                    TypeClass::Enum(_, meta) if meta.success.is_some() => ParamRole::ErrorOut,
+                    _ => ParamRole::Out,
+                }
+            };
+            roles[i] = Some(role);
+        }
+
+        roles.into_iter().map(Option::unwrap).collect()
+    }
+
+    fn generate_method(
+        &self,
+        class: &str,
+        method: &MethodInfo,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let params = &method.params;
+        let roles = self.param_roles(params);
+
+        let mut names = params
+            .iter()
+            .map(|x| to_snake_case(strip_hungarian(&x.name)))
+            .collect::<Vec<_>>();
+        // length parameters are never bound, so they can't collide
+        let bound = names
+            .iter()
+            .zip(&roles)
+            .filter(|(_, role)| !matches!(role, ParamRole::Len(_) | ParamRole::SizeOf(_)))
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        if bound.iter().collect::<HashSet<_>>().len() != bound.len() {
+            names = params.iter().map(|x| to_snake_case(&x.name)).collect();
+        }
+        let names = names.iter().map(|x| escape_keyword(x)).collect::<Vec<_>>();
+
+        let mut signature = vec![];
+        let mut prepare = vec![];
+        let mut args = vec![];
+        let mut outs = vec![];
+        let mut error = None;
+        let mut is_unsafe = false;
+
+        for ((param, role), name) in params.iter().zip(&roles).zip(&names) {
+            let ty = self.resolve_pointer_typedef(&param.ty);
+            let (base, _, _) = pointer_info(&ty);
+            let class = self.classify(base);
+            if matches!(class, TypeClass::Struct(_, true)) {
+                is_unsafe = true;
+            }
+            match role {
+                ParamRole::Value => match class {
+                    TypeClass::Enum(_, meta) => {
+                        signature.push(format!("{}: crate::{}", name, meta.name_rs));
+                        args.push(format!("{}.as_ffi()", name));
+                    }
+                    TypeClass::Handle(handle) => {
+                        signature.push(format!("{}: crate::{}", name, handle));
+                        args.push(format!("{}.0", name));
+                    }
+                    _ => {
+                        signature.push(format!("{}: {}", name, self.element_type(&class)));
+                        args.push(name.clone());
+                    }
+                },
+                ParamRole::CStr => {
+                    signature.push(format!("{}: &::std::ffi::CStr", name));
+                    args.push(format!("{}.as_ptr() as _", name));
+                }
+                ParamRole::Slice { mutable: true } => {
+                    signature.push(format!("{}: &mut [{}]", name, self.element_type(&class)));
+                    args.push(format!("crate::slice_mut_ptr({}) as _", name));
+                }
+                ParamRole::Slice { mutable: false } => {
+                    signature.push(format!("{}: &[{}]", name, self.element_type(&class)));
+                    args.push(format!("crate::slice_ptr({}) as _", name));
+                }
+                ParamRole::Bytes { mutable: true } => {
+                    signature.push(format!("{}: &mut [u8]", name));
+                    args.push(format!("crate::slice_mut_ptr({}) as _", name));
+                }
+                ParamRole::Bytes { mutable: false } => {
+                    signature.push(format!("{}: &[u8]", name));
+                    args.push(format!("crate::slice_ptr({}) as _", name));
+                }
+                &ParamRole::Len(slice) => {
+                    args.push(format!("{}.len() as _", names[slice]));
+                }
+                &ParamRole::InOutLen(slice) => {
+                    let ty = self.element_type(&class);
+                    prepare.push(format!(
+                        "let mut {} = {}.len() as {};",
+                        name, names[slice], ty
+                    ));
+                    args.push(format!("&mut {} as *mut _ as _", name));
+                    outs.push((name.clone(), ty));
+                }
+                ParamRole::SizeOf(ffi) => {
+                    args.push(format!(
+                        "::std::mem::size_of::<::openvr_sys::{}>() as _",
+                        ffi
+                    ));
+                }
+                ParamRole::Ref => {
+                    signature.push(format!("{}: &{}", name, self.element_type(&class)));
+                    args.push(format!("{} as *const _ as _", name));
+                }
+                ParamRole::Out => {
+                    args.push(format!("&mut {} as *mut _ as _", name));
+                    match class {
+                        TypeClass::Enum(ffi, meta) => {
+                            prepare.push(format!(
+                                "let mut {}: ::openvr_sys::{} = Default::default();",
+                                name, ffi
+                            ));
+                            outs.push((
+                                format!("crate::{}::from_ffi({})", meta.name_rs, name),
+                                format!("crate::{}", meta.name_rs),
+                            ));
+                        }
+                        TypeClass::Struct(ffi, _) => {
+                            prepare.push(format!(
+                                "let mut {}: ::openvr_sys::{} = ::std::mem::zeroed();",
+                                name, ffi
+                            ));
+                            if self.sized_structs.contains(self.types.typedef_struct(base)) {
+                                prepare.push(format!(
+                                    "{}.m_nSize = ::std::mem::size_of::<::openvr_sys::{}>() as _;",
+                                    name, ffi
+                                ));
+                            }
+                            outs.push((name.clone(), format!("::openvr_sys::{}", ffi)));
+                        }
+                        _ => {
+                            let ty = self.element_type(&class);
+                            prepare.push(format!("let mut {}: {} = Default::default();", name, ty));
+                            outs.push((name.clone(), ty));
+                        }
+                    }
+                }
+                ParamRole::ErrorOut => {
+                    let TypeClass::Enum(ffi, meta) = class else {
+                        unreachable!()
+                    };
+                    prepare.push(format!(
+                        "let mut {}: ::openvr_sys::{} = Default::default();",
+                        name, ffi
+                    ));
+                    args.push(format!("&mut {} as *mut _ as _", name));
+                    error = Some((name.clone(), meta));
+                }
+                ParamRole::Raw => {
+                    signature.push(format!("{}: {}", name, self.raw_pointer_type(&ty)));
+                    args.push(format!("{} as _", name));
+                    is_unsafe = true;
+                }
+            }
+        }
+
+        // return value
+        let return_type = method.return_type.trim();
+        let mut return_value = None;
+        if return_type == "const char *" {
+            return_value = Some((
+                "if ret.is_null() { None } else { Some(::std::ffi::CStr::from_ptr(ret)) }"
+                    .to_owned(),
+                "Option<&'a ::std::ffi::CStr>".to_owned(),
+            ));
+        } else {
+            match self.classify(return_type) {
+                TypeClass::Void => {}
+                TypeClass::Enum(_, meta) if meta.success.is_some() => {
+                    error = Some(("ret".to_owned(), meta));
+                }
+                TypeClass::Enum(_, meta) => {
+                    return_value = Some((
+                        format!("crate::{}::from_ffi(ret)", meta.name_rs),
+                        format!("crate::{}", meta.name_rs),
+                    ));
+                }
+                TypeClass::Handle(handle) => {
+                    return_value = Some((
+                        format!("crate::{}(ret)", handle),
+                        format!("crate::{}", handle),
+                    ));
+                }
+                class @ (TypeClass::Primitive(_) | TypeClass::Struct(_, _)) => {
+                    return_value = Some(("ret".to_owned(), self.element_type(&class)));
+                }
+                _ => panic!("unknown return type: {}", method.return_type),
+            }
+        }
+
+        // bool return value with out parameters means success
+        let bool_success = error.is_none()
+            && !outs.is_empty()
+            && matches!(&return_value, Some((_, ty)) if ty == "bool");
+
+        let mut values = vec![];
+        if !bool_success {
+            values.extend(return_value.clone());
+        }
+        values.extend(outs);
+        let (value, value_type) = match values.len() {
+            0 => ("()".to_owned(), "()".to_owned()),
+            1 => values.pop().unwrap(),
+            _ => (
+                format!(
+                    "({})",
+                    values
+                        .iter()
+                        .map(|x| x.0.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                format!(
+                    "({})",
+                    values
+                        .iter()
+                        .map(|x| x.1.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+        };
+        // the size required for the buffer is returned even if the buffer is too small
+        let buffer_error = matches!(&error, Some((name, _)) if name != "ret")
+            && matches!(&return_value, Some((_, ty)) if ty == "u32")
+            && roles.iter().any(|x| {
+                matches!(
+                    x,
+                    ParamRole::Bytes { mutable: true } | ParamRole::Slice { mutable: true }
+                )
+            });
+
+        let result_type = if let Some((_, meta)) = &error {
+            if buffer_error {
+                format!(
+                    "Result<{}, crate::low_level::BufferError<crate::{}>>",
+                    value_type, meta.name_rs
+                )
+            } else {
+                format!("Result<{}, crate::{}>", value_type, meta.name_rs)
+            }
+        } else if bool_success {
+            format!("Option<{}>", value_type)
+        } else {
+            value_type
+        };
+
+        let method_name = escape_keyword(&to_snake_case(&method.name));
+        writeln!(out, "    /// `{}::{}`", class, method.name)?;
+        if is_unsafe {
+            writeln!(out, "    ///")?;
+            writeln!(out, "    /// # Safety")?;
+            writeln!(
+                out,
+                "    /// Raw pointers including ones in structs are passed to OpenVR as is."
+            )?;
+        }
+        writeln!(
+            out,
+            "    pub {}fn {}(self{}){} {{",
+            if is_unsafe { "unsafe " } else { "" },
+            method_name,
+            signature
+                .iter()
+                .map(|x| format!(", {}", x))
+                .collect::<String>(),
+            if result_type == "()" {
+                String::new()
+            } else {
+                format!(" -> {}", result_type)
+            },
+        )?;
+        writeln!(out, "        unsafe {{")?;
+        for statement in &prepare {
+            writeln!(out, "            {}", statement)?;
+        }
+        let call = format!("self.table.{}.unwrap()({})", method.name, args.join(", "));
+
+        if return_value.is_none() && error.as_ref().is_none_or(|x| x.0 != "ret") {
+            writeln!(out, "            {};", call)?;
+        } else if value == "ret" && error.is_none() && !bool_success {
+            writeln!(out, "            {}", call)?;
+            writeln!(out, "        }}")?;
+            writeln!(out, "    }}")?;
+            return Ok(());
+        } else {
+            writeln!(out, "            let ret = {};", call)?;
+        }
+
+        if let Some((error, meta)) = &error {
+            writeln!(
+                out,
+                "            let {} = crate::{}::from_ffi({});",
+                error, meta.name_rs, error
+            )?;
+            let err = if buffer_error {
+                let field = match error.as_str() {
+                    "error" => "error".to_owned(),
+                    _ => format!("error: {}", error),
+                };
+                format!(
+                    "crate::low_level::BufferError {{ {}, required: ret }}",
+                    field
+                )
+            } else {
+                error.clone()
+            };
+            writeln!(
+                out,
+                "            if {} != crate::{}::{} {{ return Err({}); }}",
+                error,
+                meta.name_rs,
+                meta.success.as_ref().unwrap(),
+                err
+            )?;
+            writeln!(out, "            Ok({})", value)?;
+        } else if bool_success {
+            writeln!(
+                out,
+                "            if ret {{ Some({}) }} else {{ None }}",
+                value
+            )?;
+        } else if value != "()" {
+            writeln!(out, "            {}", value)?;
+        }
+        writeln!(out, "        }}")?;
+        writeln!(out, "    }}")?;
+        Ok(())
+    }
+}
+
 #[derive(Deserialize)]
 struct Json {
+    typedefs: Vec<TypedefInfo>,
     enums: Vec<EnumInfo>,
+    consts: Vec<ConstInfo>,
+    structs: Vec<StructInfo>,
+    methods: Vec<MethodInfo>,
+}
+
+#[derive(Deserialize)]
+struct TypedefInfo {
+    #[serde(rename = "typedef")]
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
 }
 
 #[derive(Deserialize)]
@@ -189,3 +1442,52 @@ struct EnumValueInfo {
     name: String,
     value: String,
 }
+
+#[derive(Deserialize)]
+struct ConstInfo {
+    #[serde(rename = "constname")]
+    name: String,
+    #[serde(rename = "consttype")]
+    ty: String,
+    #[serde(rename = "constval", default)]
+    value: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StructInfo {
+    #[serde(rename = "struct")]
+    name: String,
+    fields: Vec<FieldInfo>,
+}
+
+#[derive(Deserialize)]
+struct FieldInfo {
+    #[serde(rename = "fieldname")]
+    name: String,
+    #[serde(rename = "fieldtype")]
+    ty: String,
+}
+
+#[derive(Deserialize)]
+struct MethodInfo {
+    #[serde(rename = "classname")]
+    class: String,
+    #[serde(rename = "methodname")]
+    name: String,
+    #[serde(rename = "returntype")]
+    return_type: String,
+    #[serde(default)]
+    params: Vec<ParamInfo>,
+}
+
+#[derive(Deserialize)]
+struct ParamInfo {
+    #[serde(rename = "paramname")]
+    name: String,
+    #[serde(rename = "paramtype")]
+    ty: String,
+    array_count: Option<String>,
+    out_array_count: Option<String>,
+    out_buffer_count: Option<String>,
+    out_string: Option<String>,
+}
@@ -17,6 +17,11 @@ impl<'a> VRApplications<'a> {
     pub(crate) fn new(table: &'a openvr_sys::VR_IVRApplications_FnTable) -> Self {
         Self { table }
     }
+
+    /// Gets the low-level wrapper of this interface for the functions not covered here.
+    pub fn low_level(self) -> crate::low_level::IVRApplications<'a> {
+        crate::low_level::IVRApplications::new(self.table)
+    }
 }
 
 impl<'a> VRApplications<'a> {
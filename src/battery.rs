@@ -0,0 +1,322 @@
+use crate::{props, TrackedDeviceIndex, VRSystem};
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+/// The thresholds of notifications and the size of history of [`BatteryMonitor`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BatteryConfig {
+    /// `LowBattery` is notified when the percentage goes below this while not charging.
+    pub low_threshold: f32,
+    /// `LowBattery` is notified again after the percentage goes above `low_threshold + low_hysteresis`.
+    pub low_hysteresis: f32,
+    /// `FullyCharged` is notified when the percentage reaches this while charging.
+    pub full_threshold: f32,
+    /// The number of changes of status kept per device. `0` disables the history.
+    pub history_len: usize,
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        Self {
+            low_threshold: 0.15,
+            low_hysteresis: 0.05,
+            full_threshold: 0.99,
+            history_len: 120,
+        }
+    }
+}
+
+/// The battery status of device. `percentage` is in `0.0..=1.0`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BatteryState {
+    pub percentage: f32,
+    pub charging: bool,
+}
+
+impl BatteryState {
+    /// Reads the status. Returns `None` if the device doesn't provide battery status.
+    pub fn read(system: VRSystem, device_index: TrackedDeviceIndex) -> Option<Self> {
+        let provides = system.get_property(device_index.0, props::DEVICE_PROVIDES_BATTERY_STATUS);
+        if !provides.unwrap_or(false) {
+            return None;
+        }
+        Some(Self {
+            percentage: system
+                .get_property(device_index.0, props::DEVICE_BATTERY_PERCENTAGE)
+                .ok()?,
+            charging: system
+                .get_property(device_index.0, props::DEVICE_IS_CHARGING)
+                .unwrap_or(false),
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BatterySample {
+    pub time: Instant,
+    pub state: BatteryState,
+}
+
+/// The notification from [`BatteryMonitor`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BatteryEvent {
+    LowBattery {
+        device_index: TrackedDeviceIndex,
+        percentage: f32,
+    },
+    ChargingStarted {
+        device_index: TrackedDeviceIndex,
+    },
+    FullyCharged {
+        device_index: TrackedDeviceIndex,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+struct DeviceBattery {
+    last: Option<BatteryState>,
+    history: VecDeque<BatterySample>,
+    low_notified: bool,
+    full_notified: bool,
+}
+
+/// Monitors battery of all devices which provide battery status.
+///
+/// Pass every event to [`handle_event`](Self::handle_event) and
+/// get notifications with [`poll_event`](Self::poll_event).
+#[derive(Debug, Clone)]
+pub struct BatteryMonitor {
+    config: BatteryConfig,
+    devices: HashMap<TrackedDeviceIndex, DeviceBattery>,
+    events: VecDeque<BatteryEvent>,
+}
+
+impl BatteryMonitor {
+    /// Creates the monitor and reads all devices.
+    pub fn new(system: VRSystem, config: BatteryConfig) -> Self {
+        let mut this = Self {
+            config,
+            devices: HashMap::new(),
+            events: VecDeque::new(),
+        };
+        this.refresh(system);
+        this
+    }
+
+    pub fn config(&self) -> &BatteryConfig {
+        &self.config
+    }
+
+    /// Updates the monitor with the event. Returns `true` if the event is about battery.
+    pub fn handle_event(&mut self, system: VRSystem, event: &crate::VREvent_t) -> bool {
+        let device_index = TrackedDeviceIndex(event.trackedDeviceIndex);
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::PropertyChanged => {
+                let prop =
+                    crate::TrackedDeviceProperty::from_ffi(unsafe { event.data.property.prop });
+                let is_battery = [
+                    props::DEVICE_BATTERY_PERCENTAGE.prop(),
+                    props::DEVICE_IS_CHARGING.prop(),
+                    props::DEVICE_PROVIDES_BATTERY_STATUS.prop(),
+                ]
+                .contains(&prop);
+                if is_battery {
+                    self.refresh_device(system, device_index);
+                }
+                is_battery
+            }
+            crate::EventType::TrackedDeviceActivated | crate::EventType::TrackedDeviceUpdated => {
+                self.refresh_device(system, device_index);
+                true
+            }
+            crate::EventType::TrackedDeviceDeactivated => {
+                self.devices.remove(&device_index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reads all devices again.
+    pub fn refresh(&mut self, system: VRSystem) {
+        for device_index in 0..crate::consts::MAX_TRACKED_DEVICE_COUNT {
+            self.refresh_device(system, TrackedDeviceIndex(device_index));
+        }
+    }
+
+    pub fn refresh_device(&mut self, system: VRSystem, device_index: TrackedDeviceIndex) {
+        let state = if system.is_tracked_device_connected(device_index.0) {
+            BatteryState::read(system, device_index)
+        } else {
+            None
+        };
+        self.update(device_index, state, Instant::now());
+    }
+
+    fn update(
+        &mut self,
+        device_index: TrackedDeviceIndex,
+        state: Option<BatteryState>,
+        time: Instant,
+    ) {
+        let state = match state {
+            Some(state) => state,
+            None => {
+                self.devices.remove(&device_index);
+                return;
+            }
+        };
+        let config = self.config;
+        let device = self.devices.entry(device_index).or_default();
+        let last = device.last.replace(state);
+        if last == Some(state) {
+            return;
+        }
+
+        if state.charging && last.is_some_and(|x| !x.charging) {
+            self.events
+                .push_back(BatteryEvent::ChargingStarted { device_index });
+        }
+        if !state.charging {
+            device.full_notified = false;
+        } else if !device.full_notified && state.percentage >= config.full_threshold {
+            device.full_notified = true;
+            self.events
+                .push_back(BatteryEvent::FullyCharged { device_index });
+        }
+
+        if state.charging || state.percentage >= config.low_threshold + config.low_hysteresis {
+            device.low_notified = false;
+        } else if !device.low_notified && state.percentage < config.low_threshold {
+            device.low_notified = true;
+            self.events.push_back(BatteryEvent::LowBattery {
+                device_index,
+                percentage: state.percentage,
+            });
+        }
+
+        if config.history_len == 0 {
+            return;
+        }
+        if device.history.len() >= config.history_len {
+            device.history.pop_front();
+        }
+        device.history.push_back(BatterySample { time, state });
+    }
+
+    /// Gets the notification in order.
+    pub fn poll_event(&mut self) -> Option<BatteryEvent> {
+        self.events.pop_front()
+    }
+
+    /// The latest status of the device. `None` if the device doesn't provide battery status.
+    pub fn state(&self, device_index: TrackedDeviceIndex) -> Option<BatteryState> {
+        self.devices.get(&device_index)?.last
+    }
+
+    /// The changes of status from oldest to newest.
+    /// A sample is recorded only when the status differs from the previous one.
+    pub fn history(
+        &self,
+        device_index: TrackedDeviceIndex,
+    ) -> impl Iterator<Item = &BatterySample> + '_ {
+        self.devices
+            .get(&device_index)
+            .into_iter()
+            .flat_map(|x| x.history.iter())
+    }
+
+    /// Iterates devices with battery status
+    pub fn devices(&self) -> impl Iterator<Item = (TrackedDeviceIndex, BatteryState)> + '_ {
+        self.devices
+            .iter()
+            .filter_map(|(&index, x)| Some((index, x.last?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE: TrackedDeviceIndex = TrackedDeviceIndex(1);
+
+    fn state(percentage: f32, charging: bool) -> Option<BatteryState> {
+        Some(BatteryState {
+            percentage,
+            charging,
+        })
+    }
+
+    #[test]
+    fn notifications() {
+        let mut monitor = BatteryMonitor {
+            config: BatteryConfig {
+                history_len: 3,
+                ..BatteryConfig::default()
+            },
+            devices: HashMap::new(),
+            events: VecDeque::new(),
+        };
+        let now = Instant::now();
+        for state in [
+            state(0.2, false),
+            state(0.14, false),
+            state(0.13, false),
+            state(0.13, true),
+            state(0.99, true),
+            state(1.0, true),
+            state(0.12, false),
+        ] {
+            monitor.update(DEVICE, state, now);
+        }
+
+        let events = std::iter::from_fn(|| monitor.poll_event()).collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                BatteryEvent::LowBattery {
+                    device_index: DEVICE,
+                    percentage: 0.14,
+                },
+                BatteryEvent::ChargingStarted {
+                    device_index: DEVICE
+                },
+                BatteryEvent::FullyCharged {
+                    device_index: DEVICE
+                },
+                BatteryEvent::LowBattery {
+                    device_index: DEVICE,
+                    percentage: 0.12,
+                },
+            ]
+        );
+        assert_eq!(monitor.history(DEVICE).count(), 3);
+        assert_eq!(monitor.state(DEVICE), state(0.12, false));
+
+        monitor.update(DEVICE, None, now);
+        assert_eq!(monitor.state(DEVICE), None);
+    }
+
+    #[test]
+    fn no_history() {
+        let mut monitor = BatteryMonitor {
+            config: BatteryConfig {
+                history_len: 0,
+                ..BatteryConfig::default()
+            },
+            devices: HashMap::new(),
+            events: VecDeque::new(),
+        };
+        let now = Instant::now();
+        monitor.update(DEVICE, state(0.5, false), now);
+        monitor.update(DEVICE, state(0.5, true), now);
+        assert_eq!(monitor.history(DEVICE).count(), 0);
+        assert_eq!(monitor.state(DEVICE), state(0.5, true));
+        assert_eq!(
+            monitor.poll_event(),
+            Some(BatteryEvent::ChargingStarted {
+                device_index: DEVICE
+            })
+        );
+    }
+}
@@ -0,0 +1,412 @@
+use crate::{props, ButtonId, ControllerAxisType, TrackedDeviceIndex, VRSystem};
+use std::collections::{HashMap, VecDeque};
+
+/// The set of [`ButtonId`]s, same layout as `ulButtonPressed` of `VRControllerState_t`
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ButtonSet(u64);
+
+impl ButtonSet {
+    pub const EMPTY: ButtonSet = ButtonSet(0);
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    fn mask(button: ButtonId) -> u64 {
+        1u64.checked_shl(button.as_raw()).unwrap_or(0)
+    }
+
+    pub fn contains(self, button: ButtonId) -> bool {
+        self.0 & Self::mask(button) != 0
+    }
+
+    pub fn insert(&mut self, button: ButtonId) {
+        self.0 |= Self::mask(button);
+    }
+
+    pub fn remove(&mut self, button: ButtonId) {
+        self.0 &= !Self::mask(button);
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates buttons in the set in the order of id
+    pub fn iter(self) -> impl Iterator<Item = ButtonId> {
+        (0..64)
+            .filter(move |i| self.0 & (1 << i) != 0)
+            .map(ButtonId::from_raw)
+    }
+}
+
+impl FromIterator<ButtonId> for ButtonSet {
+    fn from_iter<T: IntoIterator<Item = ButtonId>>(iter: T) -> Self {
+        let mut set = ButtonSet::EMPTY;
+        iter.into_iter().for_each(|x| set.insert(x));
+        set
+    }
+}
+
+impl std::ops::BitOr for ButtonSet {
+    type Output = ButtonSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        ButtonSet(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for ButtonSet {
+    type Output = ButtonSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        ButtonSet(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::Sub for ButtonSet {
+    type Output = ButtonSet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        ButtonSet(self.0 & !rhs.0)
+    }
+}
+
+impl std::fmt::Debug for ButtonSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// The value of controller axis labeled with its type
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ControllerAxis {
+    pub axis_type: ControllerAxisType,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// The typed `VRControllerState_t`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ControllerState {
+    /// Changes if the state is changed
+    pub packet_num: u32,
+    pub pressed: ButtonSet,
+    pub touched: ButtonSet,
+    pub axes: [ControllerAxis; 5],
+}
+
+impl ControllerState {
+    pub fn from_raw(raw: &crate::VRControllerState_t, axis_types: [ControllerAxisType; 5]) -> Self {
+        Self {
+            packet_num: raw.unPacketNum,
+            pressed: ButtonSet(raw.ulButtonPressed),
+            touched: ButtonSet(raw.ulButtonTouched),
+            axes: [0, 1, 2, 3, 4].map(|i| ControllerAxis {
+                axis_type: axis_types[i],
+                x: raw.rAxis[i].x,
+                y: raw.rAxis[i].y,
+            }),
+        }
+    }
+
+    /// Reads the state and the axis types of the controller.
+    pub fn read(system: VRSystem, device_index: TrackedDeviceIndex) -> Option<Self> {
+        let raw = system.get_controller_state(device_index.0)?;
+        Some(Self::from_raw(&raw, axis_types(system, device_index)))
+    }
+
+    /// The first axis of the type
+    pub fn axis(&self, axis_type: ControllerAxisType) -> Option<&ControllerAxis> {
+        self.axes.iter().find(|x| x.axis_type == axis_type)
+    }
+}
+
+/// Reads the types of axes from `Prop_Axis{N}Type_Int32`
+pub fn axis_types(system: VRSystem, device_index: TrackedDeviceIndex) -> [ControllerAxisType; 5] {
+    [
+        props::AXIS0_TYPE,
+        props::AXIS1_TYPE,
+        props::AXIS2_TYPE,
+        props::AXIS3_TYPE,
+        props::AXIS4_TYPE,
+    ]
+    .map(|prop| {
+        let raw = system.get_property(device_index.0, prop).unwrap_or(0);
+        ControllerAxisType::from_raw(raw as u32)
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ButtonEventKind {
+    Pressed,
+    Released,
+    Touched,
+    Untouched,
+}
+
+/// The change of button detected by [`ControllerTracker`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ButtonEvent {
+    pub device_index: TrackedDeviceIndex,
+    pub button: ButtonId,
+    pub kind: ButtonEventKind,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct TrackedController {
+    axis_types: [ControllerAxisType; 5],
+    state: Option<ControllerState>,
+}
+
+/// Detects button changes by comparing consecutive states of controllers.
+///
+/// Call [`update`](Self::update) for each controller every frame and
+/// get changes with [`poll_event`](Self::poll_event).
+#[derive(Debug, Clone, Default)]
+pub struct ControllerTracker {
+    controllers: HashMap<TrackedDeviceIndex, TrackedController>,
+    events: VecDeque<ButtonEvent>,
+}
+
+impl ControllerTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the state of the controller. Returns the state if changed since the last update.
+    pub fn update(
+        &mut self,
+        system: VRSystem,
+        device_index: TrackedDeviceIndex,
+    ) -> Option<ControllerState> {
+        let raw = system.get_controller_state(device_index.0)?;
+        let axis_types = match self.controllers.get(&device_index) {
+            Some(controller) => controller.axis_types,
+            None => axis_types(system, device_index),
+        };
+        self.update_state(device_index, ControllerState::from_raw(&raw, axis_types))
+    }
+
+    fn update_state(
+        &mut self,
+        device_index: TrackedDeviceIndex,
+        state: ControllerState,
+    ) -> Option<ControllerState> {
+        let controller = self
+            .controllers
+            .entry(device_index)
+            .or_insert(TrackedController {
+                axis_types: state.axes.map(|x| x.axis_type),
+                state: None,
+            });
+        // the same packet has the same state
+        if controller
+            .state
+            .is_some_and(|x| x.packet_num == state.packet_num)
+        {
+            return None;
+        }
+        let last = controller.state.replace(state);
+        let last = last.unwrap_or(ControllerState {
+            pressed: ButtonSet::EMPTY,
+            touched: ButtonSet::EMPTY,
+            ..state
+        });
+
+        let changes = [
+            (state.pressed - last.pressed, ButtonEventKind::Pressed),
+            (last.pressed - state.pressed, ButtonEventKind::Released),
+            (state.touched - last.touched, ButtonEventKind::Touched),
+            (last.touched - state.touched, ButtonEventKind::Untouched),
+        ];
+        for (buttons, kind) in changes {
+            self.push_events(device_index, buttons, kind);
+        }
+        Some(state)
+    }
+
+    /// Forgets the controller when it's deactivated, and reads the axis types again when it's
+    /// updated. Returns `true` if the event is about a tracked controller.
+    ///
+    /// On deactivation, `Released` and `Untouched` are notified for the buttons still held.
+    pub fn handle_event(&mut self, system: VRSystem, event: &crate::VREvent_t) -> bool {
+        self.handle_event_with(event, |device_index| axis_types(system, device_index))
+    }
+
+    fn handle_event_with(
+        &mut self,
+        event: &crate::VREvent_t,
+        read_axis_types: impl FnOnce(TrackedDeviceIndex) -> [ControllerAxisType; 5],
+    ) -> bool {
+        let device_index = TrackedDeviceIndex(event.trackedDeviceIndex);
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::TrackedDeviceDeactivated => self.remove(device_index),
+            // properties are changed but the controller is still held
+            crate::EventType::TrackedDeviceUpdated => match self.controllers.get_mut(&device_index)
+            {
+                Some(controller) => {
+                    controller.axis_types = read_axis_types(device_index);
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn remove(&mut self, device_index: TrackedDeviceIndex) -> bool {
+        let controller = match self.controllers.remove(&device_index) {
+            Some(controller) => controller,
+            None => return false,
+        };
+        if let Some(state) = controller.state {
+            self.push_events(device_index, state.pressed, ButtonEventKind::Released);
+            self.push_events(device_index, state.touched, ButtonEventKind::Untouched);
+        }
+        true
+    }
+
+    fn push_events(
+        &mut self,
+        device_index: TrackedDeviceIndex,
+        buttons: ButtonSet,
+        kind: ButtonEventKind,
+    ) {
+        self.events.extend(buttons.iter().map(|button| ButtonEvent {
+            device_index,
+            button,
+            kind,
+        }));
+    }
+
+    /// The latest state of the controller
+    pub fn state(&self, device_index: TrackedDeviceIndex) -> Option<&ControllerState> {
+        self.controllers.get(&device_index)?.state.as_ref()
+    }
+
+    /// Gets the button change in order.
+    pub fn poll_event(&mut self) -> Option<ButtonEvent> {
+        self.events.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE: TrackedDeviceIndex = TrackedDeviceIndex(3);
+
+    fn state(packet_num: u32, pressed: &[ButtonId], touched: &[ButtonId]) -> ControllerState {
+        let mut raw: crate::VRControllerState_t = unsafe { std::mem::zeroed() };
+        raw.unPacketNum = packet_num;
+        raw.ulButtonPressed = pressed.iter().copied().collect::<ButtonSet>().bits();
+        raw.ulButtonTouched = touched.iter().copied().collect::<ButtonSet>().bits();
+        raw.rAxis[1].x = 0.5;
+        let mut axis_types = [ControllerAxisType::None; 5];
+        axis_types[1] = ControllerAxisType::Trigger;
+        ControllerState::from_raw(&raw, axis_types)
+    }
+
+    #[test]
+    fn button_set() {
+        let set = [ButtonId::Grip, ButtonId::System]
+            .into_iter()
+            .collect::<ButtonSet>();
+        assert_eq!(set.bits(), 0b101);
+        assert!(set.contains(ButtonId::Grip));
+        assert!(!set.contains(ButtonId::A));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![ButtonId::System, ButtonId::Grip]
+        );
+    }
+
+    #[test]
+    fn tracker() {
+        let trigger = ButtonId::SteamVRTrigger;
+        let grip = ButtonId::Grip;
+        let mut tracker = ControllerTracker::new();
+        assert!(tracker
+            .update_state(DEVICE, state(1, &[], &[trigger]))
+            .is_some());
+        assert!(tracker
+            .update_state(DEVICE, state(1, &[trigger], &[trigger]))
+            .is_none());
+        let updated = tracker.update_state(DEVICE, state(2, &[trigger, grip], &[trigger]));
+        assert_eq!(
+            updated
+                .unwrap()
+                .axis(ControllerAxisType::Trigger)
+                .unwrap()
+                .x,
+            0.5
+        );
+        tracker.update_state(DEVICE, state(3, &[grip], &[]));
+
+        let events = std::iter::from_fn(|| tracker.poll_event())
+            .map(|x| (x.button, x.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                (trigger, ButtonEventKind::Touched),
+                (grip, ButtonEventKind::Pressed),
+                (trigger, ButtonEventKind::Pressed),
+                (trigger, ButtonEventKind::Released),
+                (trigger, ButtonEventKind::Untouched),
+            ]
+        );
+    }
+
+    #[test]
+    fn deactivated() {
+        let trigger = ButtonId::SteamVRTrigger;
+        let mut tracker = ControllerTracker::new();
+        tracker.update_state(DEVICE, state(1, &[trigger], &[trigger]));
+        std::iter::from_fn(|| tracker.poll_event()).for_each(drop);
+
+        let mut event: crate::VREvent_t = unsafe { std::mem::zeroed() };
+        event.eventType = crate::EventType::TrackedDeviceDeactivated.as_raw();
+        event.trackedDeviceIndex = DEVICE.0;
+        assert!(tracker.handle_event_with(&event, |_| unreachable!()));
+        assert!(!tracker.handle_event_with(&event, |_| unreachable!()));
+        assert!(tracker.state(DEVICE).is_none());
+
+        let events = std::iter::from_fn(|| tracker.poll_event())
+            .map(|x| (x.device_index, x.button, x.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                (DEVICE, trigger, ButtonEventKind::Released),
+                (DEVICE, trigger, ButtonEventKind::Untouched),
+            ]
+        );
+    }
+
+    #[test]
+    fn updated() {
+        let trigger = ButtonId::SteamVRTrigger;
+        let mut tracker = ControllerTracker::new();
+        tracker.update_state(DEVICE, state(1, &[trigger], &[trigger]));
+        std::iter::from_fn(|| tracker.poll_event()).for_each(drop);
+
+        let mut event: crate::VREvent_t = unsafe { std::mem::zeroed() };
+        event.eventType = crate::EventType::TrackedDeviceUpdated.as_raw();
+        event.trackedDeviceIndex = DEVICE.0;
+        let joystick = [ControllerAxisType::Joystick; 5];
+        assert!(tracker.handle_event_with(&event, |_| joystick));
+        assert!(tracker.poll_event().is_none());
+        assert!(tracker.state(DEVICE).is_some());
+        assert_eq!(tracker.controllers[&DEVICE].axis_types, joystick);
+
+        // still held, so no new events
+        tracker.update_state(DEVICE, state(2, &[trigger], &[trigger]));
+        assert!(tracker.poll_event().is_none());
+    }
+}
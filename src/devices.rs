@@ -0,0 +1,521 @@
+use crate::{
+    props, TrackedControllerRole, TrackedDeviceClass, TrackedDeviceIndex, VROverlay, VRSystem,
+};
+use std::collections::VecDeque;
+
+/// The snapshot of connected tracked device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub index: TrackedDeviceIndex,
+    pub class: TrackedDeviceClass,
+    pub role: TrackedControllerRole,
+    pub serial: String,
+    pub model: String,
+    pub manufacturer: String,
+    pub tracking_system: String,
+}
+
+impl DeviceInfo {
+    /// Reads the device in the slot. Returns `None` if no device is connected to the slot.
+    pub fn read(system: VRSystem, index: TrackedDeviceIndex) -> Option<Self> {
+        if !system.is_tracked_device_connected(index.0) {
+            return None;
+        }
+        let string = |key| system.get_property(index.0, key).unwrap_or_default();
+        Some(Self {
+            index,
+            class: system.get_tracked_device_class(index.0),
+            role: system.get_controller_role_for_tracked_device_index(index.0),
+            serial: string(props::SERIAL_NUMBER),
+            model: string(props::MODEL_NUMBER),
+            manufacturer: string(props::MANUFACTURER_NAME),
+            tracking_system: string(props::TRACKING_SYSTEM_NAME),
+        })
+    }
+
+    fn same_except_role(&self, other: &Self) -> bool {
+        self.index == other.index
+            && self.class == other.class
+            && self.serial == other.serial
+            && self.model == other.model
+            && self.manufacturer == other.manufacturer
+            && self.tracking_system == other.tracking_system
+    }
+}
+
+/// The change of devices detected by [`DeviceRegistry`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceChange {
+    Connected(DeviceInfo),
+    Disconnected(DeviceInfo),
+    /// The properties of the device in the slot are changed, or another device took the slot.
+    Updated {
+        old: DeviceInfo,
+        new: DeviceInfo,
+    },
+    RoleChanged {
+        index: TrackedDeviceIndex,
+        old: TrackedControllerRole,
+        new: TrackedControllerRole,
+    },
+}
+
+/// The snapshot of all tracked device slots, which is kept up to date with events.
+///
+/// Pass every event to [`handle_event`](Self::handle_event) and
+/// get the changes with [`poll_change`](Self::poll_change).
+#[derive(Debug, Clone)]
+pub struct DeviceRegistry {
+    slots: Vec<Option<DeviceInfo>>,
+    changes: VecDeque<DeviceChange>,
+}
+
+impl DeviceRegistry {
+    /// Snapshots all slots. The devices connected at this time are not reported as changes.
+    pub fn new(system: VRSystem) -> Self {
+        let slots = (0..crate::consts::MAX_TRACKED_DEVICE_COUNT)
+            .map(|index| DeviceInfo::read(system, TrackedDeviceIndex(index)))
+            .collect();
+        Self {
+            slots,
+            changes: VecDeque::new(),
+        }
+    }
+
+    /// Updates the registry with the event. Returns `true` if the event is about devices.
+    pub fn handle_event(&mut self, system: VRSystem, event: &crate::VREvent_t) -> bool {
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::TrackedDeviceActivated
+            | crate::EventType::TrackedDeviceDeactivated
+            | crate::EventType::TrackedDeviceUpdated => {
+                self.refresh_slot(system, TrackedDeviceIndex(event.trackedDeviceIndex));
+                true
+            }
+            // other devices may lose their roles so check all
+            crate::EventType::TrackedDeviceRoleChanged => {
+                self.refresh_roles(system);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reads all slots again. This is useful if some events may be missed.
+    pub fn refresh(&mut self, system: VRSystem) {
+        for index in 0..self.slots.len() as u32 {
+            self.refresh_slot(system, TrackedDeviceIndex(index));
+        }
+    }
+
+    pub fn refresh_slot(&mut self, system: VRSystem, index: TrackedDeviceIndex) {
+        if (index.0 as usize) < self.slots.len() {
+            self.update_slot(index, DeviceInfo::read(system, index));
+        }
+    }
+
+    fn refresh_roles(&mut self, system: VRSystem) {
+        for index in 0..self.slots.len() {
+            if let Some(info) = &self.slots[index] {
+                let role = system.get_controller_role_for_tracked_device_index(info.index.0);
+                let new = DeviceInfo {
+                    role,
+                    ..info.clone()
+                };
+                self.update_slot(new.index, Some(new));
+            }
+        }
+    }
+
+    fn update_slot(&mut self, index: TrackedDeviceIndex, new: Option<DeviceInfo>) {
+        let slot = &mut self.slots[index.0 as usize];
+        let change = match (slot.take(), new.clone()) {
+            (None, None) => None,
+            (None, Some(new)) => Some(DeviceChange::Connected(new)),
+            (Some(old), None) => Some(DeviceChange::Disconnected(old)),
+            (Some(old), Some(new)) if old == new => None,
+            (Some(old), Some(new)) if old.role != new.role && old.same_except_role(&new) => {
+                Some(DeviceChange::RoleChanged {
+                    index,
+                    old: old.role,
+                    new: new.role,
+                })
+            }
+            (Some(old), Some(new)) => Some(DeviceChange::Updated { old, new }),
+        };
+        *slot = new;
+        self.changes.extend(change);
+    }
+
+    /// Gets the change detected by `handle_event` or `refresh` in order.
+    pub fn poll_change(&mut self) -> Option<DeviceChange> {
+        self.changes.pop_front()
+    }
+
+    pub fn get(&self, index: TrackedDeviceIndex) -> Option<&DeviceInfo> {
+        self.slots.get(index.0 as usize)?.as_ref()
+    }
+
+    /// Iterates connected devices in index order
+    pub fn iter(&self) -> impl Iterator<Item = &DeviceInfo> {
+        self.slots.iter().flatten()
+    }
+
+    pub fn of_class(&self, class: TrackedDeviceClass) -> impl Iterator<Item = &DeviceInfo> {
+        self.iter().filter(move |x| x.class == class)
+    }
+
+    pub fn find_by_serial(&self, serial: &str) -> Option<&DeviceInfo> {
+        self.iter().find(|x| x.serial == serial)
+    }
+
+    /// Finds the device with the role. If there are multiple, the one with the lowest index.
+    pub fn find_by_role(&self, role: TrackedControllerRole) -> Option<&DeviceInfo> {
+        self.iter().find(|x| x.role == role)
+    }
+}
+
+/// The identity of physical device by its serial number.
+///
+/// Unlike `TrackedDeviceIndex`, this doesn't point to another device after reconnects.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceId {
+    serial: String,
+}
+
+impl DeviceId {
+    pub fn new(serial: impl Into<String>) -> Self {
+        Self {
+            serial: serial.into(),
+        }
+    }
+
+    /// Gets the id of the device connected to the slot
+    pub fn of_index(system: VRSystem, index: TrackedDeviceIndex) -> Option<Self> {
+        if !system.is_tracked_device_connected(index.0) {
+            return None;
+        }
+        let serial = system.get_property(index.0, props::SERIAL_NUMBER).ok()?;
+        Some(Self::new(serial))
+    }
+
+    pub fn serial(&self) -> &str {
+        &self.serial
+    }
+
+    /// Finds the current index of the device in the registry
+    pub fn resolve(&self, registry: &DeviceRegistry) -> Option<TrackedDeviceIndex> {
+        registry.find_by_serial(&self.serial).map(|x| x.index)
+    }
+
+    /// Finds the current index of the device by reading serials of all slots
+    pub fn resolve_with(&self, system: VRSystem) -> Option<TrackedDeviceIndex> {
+        (0..crate::consts::MAX_TRACKED_DEVICE_COUNT)
+            .map(TrackedDeviceIndex)
+            .find(|&index| Self::of_index(system, index).as_ref() == Some(self))
+    }
+}
+
+impl From<&DeviceInfo> for DeviceId {
+    fn from(info: &DeviceInfo) -> Self {
+        Self::new(info.serial.clone())
+    }
+}
+
+/// The index of the device tracked through reconnects.
+///
+/// Call [`update`](Self::update) after [`DeviceRegistry`] handled events.
+#[derive(Debug, Clone)]
+pub struct DeviceBinding {
+    id: DeviceId,
+    index: Option<TrackedDeviceIndex>,
+}
+
+impl DeviceBinding {
+    pub fn new(id: DeviceId, registry: &DeviceRegistry) -> Self {
+        let index = id.resolve(registry);
+        Self { id, index }
+    }
+
+    pub fn id(&self) -> &DeviceId {
+        &self.id
+    }
+
+    /// The current index of the device. `None` while the device is disconnected.
+    pub fn index(&self) -> Option<TrackedDeviceIndex> {
+        self.index
+    }
+
+    /// Resolves the index again. Returns `true` if the index is changed.
+    pub fn update(&mut self, registry: &DeviceRegistry) -> bool {
+        let index = self.id.resolve(registry);
+        let changed = index != self.index;
+        self.index = index;
+        changed
+    }
+
+    /// Triggers legacy haptic pulse on the device. Returns `false` if the device is disconnected.
+    pub fn trigger_haptic_pulse(
+        &self,
+        system: VRSystem,
+        axis_id: u32,
+        duration_micro_sec: std::os::raw::c_ushort,
+    ) -> bool {
+        match self.index {
+            Some(index) => {
+                system.trigger_haptic_pulse(index.0, axis_id, duration_micro_sec);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The overlay attached to the physical device with `set_overlay_transform_tracked_device_relative`.
+///
+/// The overlay is re-attached when the device gets another index, and hidden while disconnected.
+#[derive(Clone)]
+pub struct DeviceOverlay {
+    binding: DeviceBinding,
+    handle: crate::VROverlayHandle,
+    transform: crate::HmdMatrix34_t,
+    visible: bool,
+}
+
+impl DeviceOverlay {
+    /// Attaches the overlay to the device. The overlay is shown if the device is connected.
+    pub fn new(
+        overlay: VROverlay,
+        handle: crate::VROverlayHandle,
+        binding: DeviceBinding,
+        transform: crate::HmdMatrix34_t,
+    ) -> Result<Self, crate::OverlayError> {
+        let this = Self {
+            binding,
+            handle,
+            transform,
+            visible: true,
+        };
+        this.apply(overlay)?;
+        Ok(this)
+    }
+
+    pub fn binding(&self) -> &DeviceBinding {
+        &self.binding
+    }
+
+    pub fn handle(&self) -> crate::VROverlayHandle {
+        self.handle
+    }
+
+    /// Re-attaches the overlay if the index of the device is changed.
+    pub fn update(
+        &mut self,
+        overlay: VROverlay,
+        registry: &DeviceRegistry,
+    ) -> Result<(), crate::OverlayError> {
+        if self.binding.update(registry) {
+            self.apply(overlay)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_transform(
+        &mut self,
+        overlay: VROverlay,
+        transform: crate::HmdMatrix34_t,
+    ) -> Result<(), crate::OverlayError> {
+        self.transform = transform;
+        self.apply(overlay)
+    }
+
+    /// Sets visibility of the overlay while the device is connected.
+    pub fn set_visible(
+        &mut self,
+        overlay: VROverlay,
+        visible: bool,
+    ) -> Result<(), crate::OverlayError> {
+        self.visible = visible;
+        self.apply(overlay)
+    }
+
+    fn apply(&self, overlay: VROverlay) -> Result<(), crate::OverlayError> {
+        match self.binding.index {
+            Some(index) => {
+                overlay.set_overlay_transform_tracked_device_relative(
+                    self.handle.0,
+                    index.0,
+                    &self.transform,
+                )?;
+                if self.visible {
+                    overlay.show_overlay(self.handle.0)
+                } else {
+                    overlay.hide_overlay(self.handle.0)
+                }
+            }
+            None => overlay.hide_overlay(self.handle.0),
+        }
+    }
+}
+
+/// Dumps runtime, display and all properties of connected devices as JSON for bug reports.
+///
+/// Errors of properties are recorded as `{"error": "<TrackedPropertyError>"}`.
+#[cfg(feature = "serde_json")]
+pub fn dump_json(system: VRSystem) -> serde_json::Value {
+    use serde_json::{json, Map, Value};
+
+    fn matrix34(value: crate::HmdMatrix34_t) -> Value {
+        json!(value.m)
+    }
+
+    fn read<T: crate::system::PropertyValue>(
+        system: VRSystem,
+        index: crate::TrackedDeviceIndex_t,
+        property: crate::system::Property<T>,
+        f: impl FnOnce(T) -> Value,
+    ) -> Value {
+        match system.get_property(index, property) {
+            Ok(x) => f(x),
+            Err(e) => json!({ "error": e.name().map_or_else(|| e.to_string(), str::to_owned) }),
+        }
+    }
+
+    let projection = |eye| {
+        let raw = system.get_projection_raw(eye);
+        json!({
+            "left": raw.left,
+            "right": raw.right,
+            "top": raw.top,
+            "bottom": raw.bottom,
+        })
+    };
+    let (width, height) = system.get_recommended_render_target_size();
+
+    let devices = (0..crate::consts::MAX_TRACKED_DEVICE_COUNT)
+        .filter(|&index| system.is_tracked_device_connected(index))
+        .map(|index| {
+            use crate::system::AnyProperty::*;
+            let properties = props::ALL
+                .iter()
+                .map(|&property| {
+                    let value = match property {
+                        Bool(p) => read(system, index, p, |x| json!(x)),
+                        Float(p) => read(system, index, p, |x| json!(x)),
+                        Int32(p) => read(system, index, p, |x| json!(x)),
+                        Uint64(p) => read(system, index, p, |x| json!(x)),
+                        String(p) => read(system, index, p, |x| json!(x)),
+                        Matrix34(p) => read(system, index, p, matrix34),
+                        Vector3(p) => read(system, index, p, |x| json!(x.v)),
+                        FloatArray(p) => read(system, index, p, |x| json!(x)),
+                        Int32Array(p) => read(system, index, p, |x| json!(x)),
+                        Matrix34Array(p) => {
+                            read(system, index, p, |x| x.into_iter().map(matrix34).collect())
+                        }
+                        Vector4Array(p) => {
+                            read(system, index, p, |x| x.iter().map(|x| json!(x.v)).collect())
+                        }
+                    };
+                    let name = property.prop().cpp_name().unwrap_or_default().to_owned();
+                    (name, value)
+                })
+                .collect::<Map<_, _>>();
+            json!({
+                "index": index,
+                "class": system.get_tracked_device_class(index).name(),
+                "role": system.get_controller_role_for_tracked_device_index(index).name(),
+                "properties": properties,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "runtime_version": system.get_runtime_version().to_string_lossy(),
+        "render_target_size": { "width": width, "height": height },
+        "projection": {
+            "left": projection(crate::Eye::Left),
+            "right": projection(crate::Eye::Right),
+        },
+        "devices": devices,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(index: u32, serial: &str) -> DeviceInfo {
+        DeviceInfo {
+            index: TrackedDeviceIndex(index),
+            class: TrackedDeviceClass::Controller,
+            role: TrackedControllerRole::Invalid,
+            serial: serial.to_owned(),
+            model: "model".to_owned(),
+            manufacturer: "manufacturer".to_owned(),
+            tracking_system: "lighthouse".to_owned(),
+        }
+    }
+
+    #[test]
+    fn update_slot() {
+        let mut registry = DeviceRegistry {
+            slots: vec![None; 4],
+            changes: VecDeque::new(),
+        };
+        let left = DeviceInfo {
+            role: TrackedControllerRole::LeftHand,
+            ..device(1, "LHR-1")
+        };
+
+        registry.update_slot(TrackedDeviceIndex(1), Some(device(1, "LHR-1")));
+        registry.update_slot(TrackedDeviceIndex(1), Some(device(1, "LHR-1")));
+        registry.update_slot(TrackedDeviceIndex(1), Some(left.clone()));
+        registry.update_slot(TrackedDeviceIndex(1), Some(device(1, "LHR-2")));
+        registry.update_slot(TrackedDeviceIndex(1), None);
+
+        let changes = std::iter::from_fn(|| registry.poll_change()).collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                DeviceChange::Connected(device(1, "LHR-1")),
+                DeviceChange::RoleChanged {
+                    index: TrackedDeviceIndex(1),
+                    old: TrackedControllerRole::Invalid,
+                    new: TrackedControllerRole::LeftHand,
+                },
+                DeviceChange::Updated {
+                    old: left,
+                    new: device(1, "LHR-2"),
+                },
+                DeviceChange::Disconnected(device(1, "LHR-2")),
+            ]
+        );
+
+        registry.update_slot(TrackedDeviceIndex(2), Some(device(2, "LHR-3")));
+        assert_eq!(
+            registry.find_by_serial("LHR-3").map(|x| x.index),
+            Some(TrackedDeviceIndex(2))
+        );
+        assert_eq!(registry.find_by_role(TrackedControllerRole::LeftHand), None);
+        assert_eq!(registry.iter().count(), 1);
+    }
+
+    #[test]
+    fn binding() {
+        let mut registry = DeviceRegistry {
+            slots: vec![None; 4],
+            changes: VecDeque::new(),
+        };
+        registry.update_slot(TrackedDeviceIndex(1), Some(device(1, "LHR-1")));
+        let mut binding = DeviceBinding::new(DeviceId::new("LHR-1"), &registry);
+        assert_eq!(binding.index(), Some(TrackedDeviceIndex(1)));
+
+        // reconnected to another slot and the old slot is reused by another device
+        registry.update_slot(TrackedDeviceIndex(1), Some(device(1, "LHR-2")));
+        assert!(binding.update(&registry));
+        assert_eq!(binding.index(), None);
+        registry.update_slot(TrackedDeviceIndex(3), Some(device(3, "LHR-1")));
+        assert!(binding.update(&registry));
+        assert_eq!(binding.index(), Some(TrackedDeviceIndex(3)));
+        assert!(!binding.update(&registry));
+    }
+}
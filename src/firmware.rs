@@ -0,0 +1,307 @@
+use crate::{props, FirmwareError, TrackedDeviceIndex, VRSystem};
+use std::collections::VecDeque;
+
+/// The firmware status of the device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirmwareInfo {
+    pub index: TrackedDeviceIndex,
+    pub serial: String,
+    pub update_available: bool,
+    /// The update can't be performed by [`VRSystem::perform_firmware_update`]
+    pub manual_update: bool,
+    pub manual_update_url: Option<String>,
+    pub force_update_required: bool,
+    pub tracking_firmware_version: Option<String>,
+    pub firmware_version: Option<u64>,
+}
+
+impl FirmwareInfo {
+    /// Reads the device in the slot. Returns `None` if no device is connected to the slot.
+    pub fn read(system: VRSystem, index: TrackedDeviceIndex) -> Option<Self> {
+        if !system.is_tracked_device_connected(index.0) {
+            return None;
+        }
+        let flag = |key| system.get_property(index.0, key).unwrap_or(false);
+        let manual_update_url = system
+            .get_property(index.0, props::FIRMWARE_MANUAL_UPDATE_URL)
+            .ok()
+            .filter(|x| !x.is_empty());
+        Some(Self {
+            index,
+            serial: system
+                .get_property(index.0, props::SERIAL_NUMBER)
+                .unwrap_or_default(),
+            update_available: flag(props::FIRMWARE_UPDATE_AVAILABLE),
+            manual_update: flag(props::FIRMWARE_MANUAL_UPDATE),
+            manual_update_url,
+            force_update_required: flag(props::FIRMWARE_FORCE_UPDATE_REQUIRED),
+            tracking_firmware_version: system
+                .get_property(index.0, props::TRACKING_FIRMWARE_VERSION)
+                .ok(),
+            firmware_version: system.get_property(index.0, props::FIRMWARE_VERSION).ok(),
+        })
+    }
+
+    pub fn needs_update(&self) -> bool {
+        self.update_available || self.force_update_required
+    }
+}
+
+/// Lists connected devices which have firmware updates.
+pub fn devices_needing_update(system: VRSystem) -> Vec<FirmwareInfo> {
+    (0..crate::consts::MAX_TRACKED_DEVICE_COUNT)
+        .filter_map(|index| FirmwareInfo::read(system, TrackedDeviceIndex(index)))
+        .filter(FirmwareInfo::needs_update)
+        .collect()
+}
+
+/// The progress of firmware update of a device in [`FirmwareUpdater`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    Queued,
+    /// The user have to update with `manual_update_url`. This will not be performed.
+    ManualUpdateRequired,
+    /// `perform_firmware_update` succeeded and waiting for `FirmwareUpdateStarted` event.
+    Requested,
+    InProgress,
+    Finished,
+    /// The device was deactivated before `FirmwareUpdateFinished`. The result is unknown.
+    Interrupted,
+    Failed(FirmwareError),
+}
+
+/// Performs firmware updates of devices one by one.
+///
+/// Call [`tick`](Self::tick) periodically and pass every event to [`handle_event`](Self::handle_event).
+/// The changes of status can be get with [`poll_status_change`](Self::poll_status_change).
+#[derive(Debug, Clone)]
+pub struct FirmwareUpdater {
+    statuses: Vec<(TrackedDeviceIndex, UpdateStatus)>,
+    current: Option<TrackedDeviceIndex>,
+    changes: VecDeque<(TrackedDeviceIndex, UpdateStatus)>,
+}
+
+impl FirmwareUpdater {
+    /// Queues updates of the devices like [`devices_needing_update`] returns.
+    pub fn new(devices: impl IntoIterator<Item = FirmwareInfo>) -> Self {
+        let statuses = devices
+            .into_iter()
+            .map(|info| {
+                if info.manual_update {
+                    (info.index, UpdateStatus::ManualUpdateRequired)
+                } else {
+                    (info.index, UpdateStatus::Queued)
+                }
+            })
+            .collect();
+        Self {
+            statuses,
+            current: None,
+            changes: VecDeque::new(),
+        }
+    }
+
+    /// Starts the next update if no update is running.
+    pub fn tick(&mut self, system: VRSystem) {
+        self.start_next(|index| system.perform_firmware_update(index.0))
+    }
+
+    fn start_next(
+        &mut self,
+        mut perform: impl FnMut(TrackedDeviceIndex) -> Result<(), FirmwareError>,
+    ) {
+        if self.current.is_some() {
+            return;
+        }
+        while let Some(index) = self.find(UpdateStatus::Queued) {
+            match perform(index) {
+                Ok(()) => {
+                    self.current = Some(index);
+                    self.set_status(index, UpdateStatus::Requested);
+                    return;
+                }
+                Err(e) => self.set_status(index, UpdateStatus::Failed(e)),
+            }
+        }
+    }
+
+    /// Follows firmware events. Returns `true` if the event is about the updating device.
+    pub fn handle_event(&mut self, event: &crate::VREvent_t) -> bool {
+        let index = TrackedDeviceIndex(event.trackedDeviceIndex);
+        if self.current != Some(index) {
+            return false;
+        }
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::FirmwareUpdateStarted => {
+                self.set_status(index, UpdateStatus::InProgress);
+                true
+            }
+            crate::EventType::FirmwareUpdateFinished => {
+                self.set_status(index, UpdateStatus::Finished);
+                self.current = None;
+                true
+            }
+            // move on to the next device instead of waiting forever
+            crate::EventType::TrackedDeviceDeactivated => {
+                self.set_status(index, UpdateStatus::Interrupted);
+                self.current = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Gives up waiting for the current update, e.g. when no event arrives for a long time.
+    /// The device is marked as `Interrupted` and the next update is started on next tick.
+    pub fn skip_current(&mut self) {
+        if let Some(index) = self.current.take() {
+            self.set_status(index, UpdateStatus::Interrupted);
+        }
+    }
+
+    fn find(&self, status: UpdateStatus) -> Option<TrackedDeviceIndex> {
+        self.statuses
+            .iter()
+            .find(|x| x.1 == status)
+            .map(|&(index, _)| index)
+    }
+
+    fn set_status(&mut self, index: TrackedDeviceIndex, status: UpdateStatus) {
+        if let Some(entry) = self.statuses.iter_mut().find(|x| x.0 == index) {
+            entry.1 = status;
+            self.changes.push_back((index, status));
+        }
+    }
+
+    pub fn status(&self, index: TrackedDeviceIndex) -> Option<UpdateStatus> {
+        self.statuses
+            .iter()
+            .find(|x| x.0 == index)
+            .map(|&(_, status)| status)
+    }
+
+    /// The statuses of all devices in queued order
+    pub fn statuses(&self) -> &[(TrackedDeviceIndex, UpdateStatus)] {
+        &self.statuses
+    }
+
+    /// The device being updated now
+    pub fn current(&self) -> Option<TrackedDeviceIndex> {
+        self.current
+    }
+
+    /// Gets the change of status in order.
+    pub fn poll_status_change(&mut self) -> Option<(TrackedDeviceIndex, UpdateStatus)> {
+        self.changes.pop_front()
+    }
+
+    /// Returns `true` if no update is queued or running.
+    pub fn is_done(&self) -> bool {
+        self.current.is_none() && self.find(UpdateStatus::Queued).is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(index: u32, manual_update: bool) -> FirmwareInfo {
+        FirmwareInfo {
+            index: TrackedDeviceIndex(index),
+            serial: format!("LHR-{}", index),
+            update_available: true,
+            manual_update,
+            manual_update_url: None,
+            force_update_required: false,
+            tracking_firmware_version: None,
+            firmware_version: None,
+        }
+    }
+
+    fn event(index: u32, event_type: crate::EventType) -> crate::VREvent_t {
+        let mut event: crate::VREvent_t = unsafe { std::mem::zeroed() };
+        event.eventType = event_type.as_raw();
+        event.trackedDeviceIndex = index;
+        event
+    }
+
+    #[test]
+    fn one_by_one() {
+        let mut updater = FirmwareUpdater::new([
+            info(1, false),
+            info(2, true),
+            info(3, false),
+            info(4, false),
+        ]);
+        let perform = |index| match index {
+            TrackedDeviceIndex(3) => Err(FirmwareError::Fail),
+            _ => Ok(()),
+        };
+
+        updater.start_next(perform);
+        assert_eq!(updater.current(), Some(TrackedDeviceIndex(1)));
+        updater.start_next(perform);
+        assert_eq!(
+            updater.status(TrackedDeviceIndex(3)),
+            Some(UpdateStatus::Queued)
+        );
+
+        assert!(!updater.handle_event(&event(4, crate::EventType::FirmwareUpdateStarted)));
+        assert!(updater.handle_event(&event(1, crate::EventType::FirmwareUpdateStarted)));
+        assert!(updater.handle_event(&event(1, crate::EventType::FirmwareUpdateFinished)));
+        updater.start_next(perform);
+        assert_eq!(updater.current(), Some(TrackedDeviceIndex(4)));
+        updater.handle_event(&event(4, crate::EventType::FirmwareUpdateFinished));
+        assert!(updater.is_done());
+
+        assert_eq!(
+            updater.statuses(),
+            &[
+                (TrackedDeviceIndex(1), UpdateStatus::Finished),
+                (TrackedDeviceIndex(2), UpdateStatus::ManualUpdateRequired),
+                (
+                    TrackedDeviceIndex(3),
+                    UpdateStatus::Failed(FirmwareError::Fail)
+                ),
+                (TrackedDeviceIndex(4), UpdateStatus::Finished),
+            ]
+        );
+        let changes = std::iter::from_fn(|| updater.poll_status_change()).collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                (TrackedDeviceIndex(1), UpdateStatus::Requested),
+                (TrackedDeviceIndex(1), UpdateStatus::InProgress),
+                (TrackedDeviceIndex(1), UpdateStatus::Finished),
+                (
+                    TrackedDeviceIndex(3),
+                    UpdateStatus::Failed(FirmwareError::Fail)
+                ),
+                (TrackedDeviceIndex(4), UpdateStatus::Requested),
+                (TrackedDeviceIndex(4), UpdateStatus::Finished),
+            ]
+        );
+    }
+
+    #[test]
+    fn deactivated() {
+        let mut updater = FirmwareUpdater::new([info(1, false), info(2, false)]);
+        updater.start_next(|_| Ok(()));
+        assert!(updater.handle_event(&event(1, crate::EventType::FirmwareUpdateStarted)));
+        assert!(updater.handle_event(&event(1, crate::EventType::TrackedDeviceDeactivated)));
+        assert_eq!(
+            updater.status(TrackedDeviceIndex(1)),
+            Some(UpdateStatus::Interrupted)
+        );
+        assert_eq!(updater.current(), None);
+
+        updater.start_next(|_| Ok(()));
+        assert_eq!(updater.current(), Some(TrackedDeviceIndex(2)));
+        updater.skip_current();
+        assert_eq!(
+            updater.status(TrackedDeviceIndex(2)),
+            Some(UpdateStatus::Interrupted)
+        );
+        assert!(updater.is_done());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::system::TimeSinceLastVsync;
+use crate::{props, DevicePose, TrackingUniverseOrigin, VRSystem};
+
+const DEFAULT_DISPLAY_FREQUENCY: f32 = 90.0;
+
+/// Computes how far to predict poses so that they match the time photons leave the display.
+///
+/// The display timing is read from the HMD properties. Pass every event to
+/// [`handle_event`](Self::handle_event) to follow the changes of them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FrameClock {
+    display_frequency: f32,
+    seconds_from_vsync_to_photons: f32,
+}
+
+impl FrameClock {
+    pub fn new(system: VRSystem) -> Self {
+        let mut this = Self::with_timing(DEFAULT_DISPLAY_FREQUENCY, 0.0);
+        this.refresh(system);
+        this
+    }
+
+    /// Creates the clock with known display timing.
+    pub fn with_timing(display_frequency: f32, seconds_from_vsync_to_photons: f32) -> Self {
+        Self {
+            display_frequency,
+            seconds_from_vsync_to_photons,
+        }
+    }
+
+    /// Reads the display timing of the HMD again.
+    /// The values not available are kept.
+    pub fn refresh(&mut self, system: VRSystem) {
+        let hmd = crate::consts::TRACKED_DEVICE_INDEX_HMD.0;
+        if let Ok(frequency) = system.get_property(hmd, props::DISPLAY_FREQUENCY) {
+            if frequency > 0.0 {
+                self.display_frequency = frequency;
+            }
+        }
+        if let Ok(seconds) = system.get_property(hmd, props::SECONDS_FROM_VSYNC_TO_PHOTONS) {
+            self.seconds_from_vsync_to_photons = seconds;
+        }
+    }
+
+    /// Reads the timing again if changed by the event. Returns `true` if refreshed.
+    pub fn handle_event(&mut self, system: VRSystem, event: &crate::VREvent_t) -> bool {
+        if event.trackedDeviceIndex != crate::consts::TRACKED_DEVICE_INDEX_HMD.0 {
+            return false;
+        }
+        let refresh = match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::PropertyChanged => {
+                let prop =
+                    crate::TrackedDeviceProperty::from_ffi(unsafe { event.data.property.prop });
+                prop == props::DISPLAY_FREQUENCY.prop()
+                    || prop == props::SECONDS_FROM_VSYNC_TO_PHOTONS.prop()
+            }
+            crate::EventType::TrackedDeviceActivated | crate::EventType::TrackedDeviceUpdated => {
+                true
+            }
+            _ => false,
+        };
+        if refresh {
+            self.refresh(system);
+        }
+        refresh
+    }
+
+    pub fn display_frequency(&self) -> f32 {
+        self.display_frequency
+    }
+
+    pub fn seconds_from_vsync_to_photons(&self) -> f32 {
+        self.seconds_from_vsync_to_photons
+    }
+
+    pub fn frame_duration(&self) -> f32 {
+        self.display_frequency.recip()
+    }
+
+    /// Computes the prediction time from the time since last vsync.
+    ///
+    /// The frame rendered now will be shown at the next vsync, then photons leave the display
+    /// `seconds_from_vsync_to_photons` after that.
+    pub fn prediction_seconds(&self, vsync: TimeSinceLastVsync) -> f32 {
+        let frame_duration = self.frame_duration();
+        let since_vsync = vsync.seconds_since_last_vsync.clamp(0.0, frame_duration);
+        frame_duration - since_vsync + self.seconds_from_vsync_to_photons
+    }
+
+    /// Computes the prediction time for the frame being rendered now.
+    /// If the vsync timing is not available, predicts for the whole frame.
+    pub fn predicted_seconds_to_photons(&self, system: VRSystem) -> f32 {
+        match system.get_time_since_last_vsync() {
+            Some(vsync) => self.prediction_seconds(vsync),
+            None => self.frame_duration() + self.seconds_from_vsync_to_photons,
+        }
+    }
+
+    /// Gets the poses of all devices predicted to the photon time of the frame being rendered now.
+    pub fn predicted_poses(
+        &self,
+        system: VRSystem,
+        origin: TrackingUniverseOrigin,
+    ) -> [Option<DevicePose>; crate::consts::MAX_TRACKED_DEVICE_COUNT as usize] {
+        system.poses(origin, self.predicted_seconds_to_photons(system))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vsync(seconds_since_last_vsync: f32) -> TimeSinceLastVsync {
+        TimeSinceLastVsync {
+            seconds_since_last_vsync,
+            frame_counter: 0,
+        }
+    }
+
+    #[test]
+    fn prediction() {
+        let clock = FrameClock::with_timing(100.0, 0.005);
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-6;
+        assert!(close(clock.prediction_seconds(vsync(0.0)), 0.015));
+        assert!(close(clock.prediction_seconds(vsync(0.004)), 0.011));
+        // missed vsync
+        assert!(close(clock.prediction_seconds(vsync(0.02)), 0.005));
+    }
+}
@@ -0,0 +1,380 @@
+use crate::{TrackedDeviceIndex, VRActionHandle, VRInput, VRInputValueHandle, VRSystem};
+use std::time::{Duration, Instant};
+
+/// The frequency used by the built-in patterns in Hz
+pub const DEFAULT_FREQUENCY: f32 = 160.0;
+
+/// The longest legacy haptic pulse. Longer pulses are ignored by the runtime.
+pub const MAX_LEGACY_PULSE: Duration = Duration::from_micros(3999);
+
+/// The device or the action haptics are played on
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HapticTarget {
+    /// Legacy `trigger_haptic_pulse`. The amplitude is converted to the length of the pulse.
+    Device {
+        device_index: TrackedDeviceIndex,
+        axis_id: u32,
+    },
+    /// `trigger_haptic_vibration_action` of the action API
+    Action {
+        action: VRActionHandle,
+        restrict_to_device: VRInputValueHandle,
+    },
+}
+
+/// The point of haptic envelope. `amplitude` is in `0.0..=1.0`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Keyframe {
+    pub time: Duration,
+    pub amplitude: f32,
+    pub frequency: f32,
+}
+
+impl Keyframe {
+    pub fn new(time: Duration, amplitude: f32, frequency: f32) -> Self {
+        Self {
+            time,
+            amplitude,
+            frequency,
+        }
+    }
+}
+
+/// The haptic envelope linearly interpolated between keyframes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HapticPattern {
+    keyframes: Vec<Keyframe>,
+}
+
+impl HapticPattern {
+    /// Creates the pattern from keyframes. Two keyframes at the same time make a step.
+    pub fn from_keyframes(mut keyframes: Vec<Keyframe>) -> Self {
+        keyframes.sort_by_key(|x| x.time);
+        Self { keyframes }
+    }
+
+    /// Vibrates with the constant amplitude
+    pub fn constant(duration: Duration, amplitude: f32, frequency: f32) -> Self {
+        Self::from_keyframes(vec![
+            Keyframe::new(Duration::ZERO, amplitude, frequency),
+            Keyframe::new(duration, amplitude, frequency),
+        ])
+    }
+
+    /// Changes the amplitude linearly from `from` to `to`
+    pub fn ramp(duration: Duration, from: f32, to: f32) -> Self {
+        Self::from_keyframes(vec![
+            Keyframe::new(Duration::ZERO, from, DEFAULT_FREQUENCY),
+            Keyframe::new(duration, to, DEFAULT_FREQUENCY),
+        ])
+    }
+
+    /// A short and strong pulse
+    pub fn click() -> Self {
+        Self::constant(Duration::from_millis(10), 1.0, DEFAULT_FREQUENCY)
+    }
+
+    pub fn buzz(duration: Duration, amplitude: f32) -> Self {
+        Self::constant(duration, amplitude, DEFAULT_FREQUENCY)
+    }
+
+    /// Two pulses like a heartbeat
+    pub fn heartbeat() -> Self {
+        Self::buzz(Duration::from_millis(60), 1.0).then(
+            Duration::from_millis(100),
+            Self::buzz(Duration::from_millis(60), 0.6),
+        )
+    }
+
+    /// Plays `next` after this and the gap.
+    pub fn then(mut self, gap: Duration, next: HapticPattern) -> Self {
+        let end = self.duration();
+        let frequency = self
+            .keyframes
+            .last()
+            .map_or(DEFAULT_FREQUENCY, |x| x.frequency);
+        self.keyframes.push(Keyframe::new(end, 0.0, frequency));
+        self.keyframes
+            .push(Keyframe::new(end + gap, 0.0, frequency));
+        self.keyframes
+            .extend(next.keyframes.into_iter().map(|x| Keyframe {
+                time: end + gap + x.time,
+                ..x
+            }));
+        self
+    }
+
+    /// Plays this `count` times with the gap. Empty if `count` is 0.
+    pub fn repeat(self, count: usize, gap: Duration) -> Self {
+        if count == 0 {
+            return Self::from_keyframes(Vec::new());
+        }
+        let mut result = self.clone();
+        for _ in 1..count {
+            result = result.then(gap, self.clone());
+        }
+        result
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.keyframes.last().map_or(Duration::ZERO, |x| x.time)
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// The amplitude and the frequency at the time. `None` if the time is after the end.
+    pub fn sample(&self, time: Duration) -> Option<(f32, f32)> {
+        let next = self.keyframes.partition_point(|x| x.time <= time);
+        let prev = self.keyframes.get(next.checked_sub(1)?)?;
+        let next = match self.keyframes.get(next) {
+            Some(next) => next,
+            None if time == prev.time => return Some((prev.amplitude, prev.frequency)),
+            None => return None,
+        };
+        let t = (time - prev.time).as_secs_f32() / (next.time - prev.time).as_secs_f32();
+        Some((
+            prev.amplitude + (next.amplitude - prev.amplitude) * t,
+            prev.frequency + (next.frequency - prev.frequency) * t,
+        ))
+    }
+}
+
+/// The id of the pattern being played, used to cancel it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HapticId(u64);
+
+/// The vibration sent in a frame
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Vibration {
+    pub duration: Duration,
+    pub amplitude: f32,
+    pub frequency: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Playing {
+    id: HapticId,
+    target: HapticTarget,
+    pattern: HapticPattern,
+    start: Instant,
+}
+
+/// Plays haptic patterns by sending short vibrations every frame.
+///
+/// Overlapping patterns on the same target are mixed by taking the strongest one.
+/// Call [`tick`](Self::tick) every frame.
+#[derive(Debug, Clone)]
+pub struct HapticScheduler {
+    frame_duration: Duration,
+    next_id: u64,
+    playing: Vec<Playing>,
+}
+
+impl HapticScheduler {
+    /// `frame_duration` is the interval of `tick` and the length of each vibration.
+    pub fn new(frame_duration: Duration) -> Self {
+        Self {
+            frame_duration,
+            next_id: 0,
+            playing: Vec::new(),
+        }
+    }
+
+    /// Starts playing the pattern now.
+    pub fn play(&mut self, target: HapticTarget, pattern: HapticPattern) -> HapticId {
+        self.play_at(target, pattern, Instant::now())
+    }
+
+    /// Starts playing the pattern at the time.
+    pub fn play_at(
+        &mut self,
+        target: HapticTarget,
+        pattern: HapticPattern,
+        start: Instant,
+    ) -> HapticId {
+        let id = HapticId(self.next_id);
+        self.next_id += 1;
+        self.playing.push(Playing {
+            id,
+            target,
+            pattern,
+            start,
+        });
+        id
+    }
+
+    /// Stops the pattern. Returns `false` if it's already finished.
+    pub fn cancel(&mut self, id: HapticId) -> bool {
+        let len = self.playing.len();
+        self.playing.retain(|x| x.id != id);
+        len != self.playing.len()
+    }
+
+    /// Stops all patterns on the target.
+    pub fn cancel_target(&mut self, target: HapticTarget) {
+        self.playing.retain(|x| x.target != target);
+    }
+
+    pub fn cancel_all(&mut self) {
+        self.playing.clear();
+    }
+
+    pub fn is_playing(&self, id: HapticId) -> bool {
+        self.playing.iter().any(|x| x.id == id)
+    }
+
+    /// Sends the vibrations of this frame.
+    pub fn tick(&mut self, system: VRSystem, input: VRInput) -> Result<(), crate::InputError> {
+        self.tick_at(Instant::now(), |target, vibration| match target {
+            HapticTarget::Device {
+                device_index,
+                axis_id,
+            } => {
+                let pulse = MAX_LEGACY_PULSE.mul_f32(vibration.amplitude);
+                system.trigger_haptic_pulse_duration(device_index.0, axis_id, pulse);
+                Ok(())
+            }
+            HapticTarget::Action {
+                action,
+                restrict_to_device,
+            } => input.trigger_haptic_vibration_action_duration(
+                action.0,
+                Duration::ZERO,
+                vibration.duration,
+                vibration.frequency,
+                vibration.amplitude,
+                restrict_to_device.0,
+            ),
+        })
+    }
+
+    fn tick_at(
+        &mut self,
+        now: Instant,
+        mut output: impl FnMut(HapticTarget, Vibration) -> Result<(), crate::InputError>,
+    ) -> Result<(), crate::InputError> {
+        self.playing
+            .retain(|x| now.saturating_duration_since(x.start) <= x.pattern.duration());
+
+        let mut vibrations: Vec<(HapticTarget, Vibration)> = Vec::new();
+        for playing in &self.playing {
+            let time = match now.checked_duration_since(playing.start) {
+                Some(time) => time,
+                None => continue, // not started yet
+            };
+            let (amplitude, frequency) = match playing.pattern.sample(time) {
+                Some(sample) => sample,
+                None => continue,
+            };
+            // NaN would panic in `Duration::mul_f32` and never lose in the mixing below
+            let amplitude = if amplitude.is_nan() {
+                0.0
+            } else {
+                amplitude.clamp(0.0, 1.0)
+            };
+            let vibration = Vibration {
+                duration: self.frame_duration,
+                amplitude,
+                frequency,
+            };
+            match vibrations.iter_mut().find(|x| x.0 == playing.target) {
+                Some(entry) if entry.1.amplitude < amplitude => entry.1 = vibration,
+                Some(_) => {}
+                None => vibrations.push((playing.target, vibration)),
+            }
+        }
+
+        let mut result = Ok(());
+        for (target, vibration) in vibrations {
+            if vibration.amplitude > 0.0 {
+                result = result.and(output(target, vibration));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern() {
+        let ms = Duration::from_millis;
+        let pattern = HapticPattern::ramp(ms(100), 0.0, 1.0).then(ms(50), HapticPattern::click());
+        assert_eq!(pattern.duration(), ms(160));
+        assert_eq!(pattern.sample(ms(50)).unwrap().0, 0.5);
+        assert_eq!(pattern.sample(ms(100)).unwrap().0, 0.0);
+        assert_eq!(pattern.sample(ms(120)).unwrap().0, 0.0);
+        assert_eq!(pattern.sample(ms(150)).unwrap().0, 1.0);
+        assert_eq!(pattern.sample(ms(160)).unwrap().0, 1.0);
+        assert_eq!(pattern.sample(ms(161)), None);
+        assert_eq!(HapticPattern::click().repeat(3, ms(10)).duration(), ms(50));
+        let empty = HapticPattern::click().repeat(0, ms(10));
+        assert!(empty.keyframes().is_empty());
+        assert_eq!(empty.duration(), Duration::ZERO);
+        assert_eq!(empty.sample(Duration::ZERO), None);
+    }
+
+    #[test]
+    fn scheduler() {
+        let ms = Duration::from_millis;
+        let device = HapticTarget::Device {
+            device_index: TrackedDeviceIndex(1),
+            axis_id: 0,
+        };
+        let action = HapticTarget::Action {
+            action: VRActionHandle(2),
+            restrict_to_device: VRInputValueHandle(0),
+        };
+        let start = Instant::now();
+        let mut scheduler = HapticScheduler::new(ms(11));
+        let weak = scheduler.play_at(device, HapticPattern::buzz(ms(100), 0.3), start);
+        let strong = scheduler.play_at(device, HapticPattern::buzz(ms(20), 0.8), start);
+        scheduler.play_at(action, HapticPattern::click(), start + ms(50));
+
+        let tick = |scheduler: &mut HapticScheduler, time: Duration| {
+            let mut sent = Vec::new();
+            scheduler
+                .tick_at(start + time, |target, vibration| {
+                    sent.push((target, vibration.amplitude));
+                    Ok(())
+                })
+                .unwrap();
+            sent
+        };
+
+        assert_eq!(tick(&mut scheduler, ms(10)), vec![(device, 0.8)]);
+        assert_eq!(tick(&mut scheduler, ms(30)), vec![(device, 0.3)]);
+        assert!(!scheduler.is_playing(strong));
+        assert_eq!(
+            tick(&mut scheduler, ms(55)),
+            vec![(device, 0.3), (action, 1.0)]
+        );
+        assert!(scheduler.cancel(weak));
+        assert_eq!(tick(&mut scheduler, ms(70)), vec![]);
+    }
+
+    #[test]
+    fn nan_amplitude() {
+        let ms = Duration::from_millis;
+        let device = HapticTarget::Device {
+            device_index: TrackedDeviceIndex(1),
+            axis_id: 0,
+        };
+        let start = Instant::now();
+        let mut scheduler = HapticScheduler::new(ms(11));
+        scheduler.play_at(device, HapticPattern::buzz(ms(100), f32::NAN), start);
+        scheduler.play_at(device, HapticPattern::buzz(ms(100), 0.5), start);
+        let mut amplitudes = Vec::new();
+        scheduler
+            .tick_at(start + ms(10), |_, vibration| {
+                amplitudes.push(vibration.amplitude);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(amplitudes, vec![0.5]);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{Eye, HiddenAreaMeshType, VRSystem};
+
+/// The owned hidden area mesh.
+///
+/// The vertices are in texture coordinates `0.0..=1.0` with `(0, 0)` at the top left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HiddenAreaMesh {
+    pub mesh_type: HiddenAreaMeshType,
+    /// The triangles of `Standard` and `Inverse` meshes
+    pub triangles: Vec<[[f32; 2]; 3]>,
+    /// The vertices of the loop of `LineLoop` mesh
+    pub line_loop: Vec<[f32; 2]>,
+}
+
+impl HiddenAreaMesh {
+    /// Copies the mesh from the runtime memory.
+    pub fn read(system: VRSystem, eye: Eye, mesh_type: HiddenAreaMeshType) -> Self {
+        let raw = system.get_hidden_area_mesh(eye, mesh_type);
+        // the count is the number of vertices for line loop
+        let vertex_count = match mesh_type {
+            HiddenAreaMeshType::LineLoop => raw.unTriangleCount as usize,
+            _ => raw.unTriangleCount as usize * 3,
+        };
+        let vertices: Vec<[f32; 2]> = if raw.pVertexData.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(raw.pVertexData, vertex_count) }
+                .iter()
+                .map(|x| x.v)
+                .collect()
+        };
+        Self::from_vertices(mesh_type, vertices)
+    }
+
+    /// Creates the mesh from vertices in the layout of `HiddenAreaMesh_t`.
+    pub fn from_vertices(mesh_type: HiddenAreaMeshType, vertices: Vec<[f32; 2]>) -> Self {
+        match mesh_type {
+            HiddenAreaMeshType::LineLoop => Self {
+                mesh_type,
+                triangles: Vec::new(),
+                line_loop: vertices,
+            },
+            _ => Self {
+                mesh_type,
+                triangles: vertices
+                    .chunks_exact(3)
+                    .map(|x| [x[0], x[1], x[2]])
+                    .collect(),
+                line_loop: Vec::new(),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty() && self.line_loop.is_empty()
+    }
+
+    /// Rasterizes the mesh into the mask of `width` x `height` pixels.
+    ///
+    /// A pixel is covered if its center is inside a triangle, or inside the loop for `LineLoop`.
+    /// For `Standard` mesh, covered pixels are never seen by the user.
+    pub fn rasterize(&self, width: usize, height: usize) -> CoverageMask {
+        let mut mask = CoverageMask {
+            width,
+            height,
+            covered: vec![false; width * height],
+        };
+        for triangle in &self.triangles {
+            mask.fill_triangle(triangle);
+        }
+        if self.line_loop.len() >= 3 {
+            mask.fill_polygon(&self.line_loop);
+        }
+        mask
+    }
+}
+
+/// The coverage of a mesh per pixel, row by row from the top
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageMask {
+    width: usize,
+    height: usize,
+    covered: Vec<bool>,
+}
+
+impl CoverageMask {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_covered(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.covered[y * self.width + x]
+    }
+
+    /// The coverage of all pixels, row by row from the top
+    pub fn as_slice(&self) -> &[bool] {
+        &self.covered
+    }
+
+    /// The number of covered pixels
+    pub fn count(&self) -> usize {
+        self.covered.iter().filter(|&&x| x).count()
+    }
+
+    /// Swaps covered and not covered pixels
+    pub fn invert(&mut self) {
+        self.covered.iter_mut().for_each(|x| *x = !*x);
+    }
+
+    /// Converts into 8-bit grayscale image: 255 for covered and 0 for not covered
+    pub fn to_luma8(&self) -> Vec<u8> {
+        self.covered
+            .iter()
+            .map(|&x| if x { 255 } else { 0 })
+            .collect()
+    }
+
+    fn pixel_center(&self, x: usize, y: usize) -> [f32; 2] {
+        [
+            (x as f32 + 0.5) / self.width as f32,
+            (y as f32 + 0.5) / self.height as f32,
+        ]
+    }
+
+    fn fill_triangle(&mut self, [a, b, c]: &[[f32; 2]; 3]) {
+        fn edge(from: [f32; 2], to: [f32; 2], p: [f32; 2]) -> f32 {
+            (to[0] - from[0]) * (p[1] - from[1]) - (to[1] - from[1]) * (p[0] - from[0])
+        }
+        let (x_range, y_range) = self.pixel_range(&[*a, *b, *c]);
+        for y in y_range {
+            for x in x_range.clone() {
+                let p = self.pixel_center(x, y);
+                let e = [edge(*a, *b, p), edge(*b, *c, p), edge(*c, *a, p)];
+                // either winding
+                if e.iter().all(|&x| x >= 0.0) || e.iter().all(|&x| x <= 0.0) {
+                    self.covered[y * self.width + x] = true;
+                }
+            }
+        }
+    }
+
+    /// Fills the polygon with even-odd rule
+    fn fill_polygon(&mut self, vertices: &[[f32; 2]]) {
+        let (x_range, y_range) = self.pixel_range(vertices);
+        for y in y_range {
+            for x in x_range.clone() {
+                let [px, py] = self.pixel_center(x, y);
+                let mut inside = false;
+                let mut prev = vertices[vertices.len() - 1];
+                for &current in vertices {
+                    if (current[1] > py) != (prev[1] > py) {
+                        let cross_x = current[0]
+                            + (py - current[1]) * (prev[0] - current[0]) / (prev[1] - current[1]);
+                        if px < cross_x {
+                            inside = !inside;
+                        }
+                    }
+                    prev = current;
+                }
+                if inside {
+                    self.covered[y * self.width + x] = true;
+                }
+            }
+        }
+    }
+
+    /// The range of pixels to test for the vertices
+    fn pixel_range(
+        &self,
+        vertices: &[[f32; 2]],
+    ) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let range = |axis: usize, size: usize| {
+            let min = vertices
+                .iter()
+                .map(|x| x[axis])
+                .fold(f32::INFINITY, f32::min);
+            let max = vertices
+                .iter()
+                .map(|x| x[axis])
+                .fold(f32::NEG_INFINITY, f32::max);
+            let start = (min * size as f32).floor().clamp(0.0, size as f32) as usize;
+            let end = (max * size as f32).ceil().clamp(0.0, size as f32) as usize;
+            start..end
+        };
+        (range(0, self.width), range(1, self.height))
+    }
+}
+
+/// Rasterizes the hidden area meshes of both eyes. Returns masks for left and right eyes.
+pub fn eye_masks(
+    system: VRSystem,
+    mesh_type: HiddenAreaMeshType,
+    width: usize,
+    height: usize,
+) -> [CoverageMask; 2] {
+    [Eye::Left, Eye::Right]
+        .map(|eye| HiddenAreaMesh::read(system, eye, mesh_type).rasterize(width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rasterize() {
+        // the left half as two triangles
+        let vertices = vec![
+            [0.0, 0.0],
+            [0.5, 0.0],
+            [0.0, 1.0],
+            [0.5, 0.0],
+            [0.5, 1.0],
+            [0.0, 1.0],
+        ];
+        let mesh = HiddenAreaMesh::from_vertices(HiddenAreaMeshType::Standard, vertices);
+        assert_eq!(mesh.triangles.len(), 2);
+        let mut mask = mesh.rasterize(4, 2);
+        assert_eq!(mask.to_luma8(), vec![255, 255, 0, 0, 255, 255, 0, 0]);
+        mask.invert();
+        assert_eq!(mask.count(), 4);
+        assert!(mask.is_covered(3, 1));
+
+        // the diamond at the center
+        let line_loop = vec![[0.5, 0.0], [1.0, 0.5], [0.5, 1.0], [0.0, 0.5]];
+        let mesh = HiddenAreaMesh::from_vertices(HiddenAreaMeshType::LineLoop, line_loop);
+        let mask = mesh.rasterize(4, 4);
+        for (x, y) in [(0, 0), (3, 0), (0, 3), (3, 3)] {
+            assert!(!mask.is_covered(x, y), "({}, {})", x, y);
+        }
+        for (x, y) in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            assert!(mask.is_covered(x, y), "({}, {})", x, y);
+        }
+    }
+}
@@ -20,6 +20,11 @@ impl<'a> VRInput<'a> {
     pub(crate) fn new(table: &'a openvr_sys::VR_IVRInput_FnTable) -> Self {
         Self { table }
     }
+
+    /// Gets the low-level wrapper of this interface for the functions not covered here.
+    pub fn low_level(self) -> crate::low_level::IVRInput<'a> {
+        crate::low_level::IVRInput::new(self.table)
+    }
 }
 
 impl<'a> VRInput<'a> {
@@ -117,7 +122,7 @@ impl<'a> VRInput<'a> {
             let mut result = zeroed();
             mk_err(self.table.GetPoseActionDataRelativeToNow.unwrap()(
                 action,
-                origin.as_raw(),
+                origin.as_ffi(),
                 predicted_seconds_from_now,
                 &mut result,
                 size_of::<crate::InputPoseActionData_t>() as u32,
@@ -137,7 +142,7 @@ impl<'a> VRInput<'a> {
             let mut result = zeroed();
             mk_err(self.table.GetPoseActionDataForNextFrame.unwrap()(
                 action,
-                origin.as_raw(),
+                origin.as_ffi(),
                 &mut result,
                 size_of::<crate::InputPoseActionData_t>() as u32,
                 restrict_to_device,
@@ -165,12 +170,12 @@ impl<'a> VRInput<'a> {
         unsafe {
             let mut result = zeroed();
             mk_err(self.table.GetDominantHand.unwrap()(&mut result))?;
-            Ok(crate::TrackedControllerRole::from_raw(result))
+            Ok(crate::TrackedControllerRole::from_ffi(result))
         }
     }
 
     pub fn set_dominant_hand(self, dominant_hand: crate::TrackedControllerRole) -> Result {
-        unsafe { mk_err(self.table.SetDominantHand.unwrap()(dominant_hand.as_raw())) }
+        unsafe { mk_err(self.table.SetDominantHand.unwrap()(dominant_hand.as_ffi())) }
     }
 
     pub fn get_bone_count(self, action: crate::VRActionHandle_t) -> Result<u32> {
@@ -226,8 +231,8 @@ impl<'a> VRInput<'a> {
         unsafe {
             mk_err(self.table.GetSkeletalReferenceTransforms.unwrap()(
                 action,
-                transform_space.as_raw(),
-                reference_pose.as_raw(),
+                transform_space.as_ffi(),
+                reference_pose.as_ffi(),
                 transforms.as_mut_ptr(),
                 transforms.len() as _,
             ))
@@ -244,7 +249,7 @@ impl<'a> VRInput<'a> {
                 action,
                 &mut result,
             ))?;
-            Ok(crate::SkeletalTrackingLevel::from_raw(result))
+            Ok(crate::SkeletalTrackingLevel::from_ffi(result))
         }
     }
 
@@ -258,8 +263,8 @@ impl<'a> VRInput<'a> {
         unsafe {
             mk_err(self.table.GetSkeletalBoneData.unwrap()(
                 action,
-                transform_space.as_raw(),
-                motion_range.as_raw(),
+                transform_space.as_ffi(),
+                motion_range.as_ffi(),
                 transform_array.as_mut_ptr(),
                 transform_array.len() as _,
             ))
@@ -275,7 +280,7 @@ impl<'a> VRInput<'a> {
             let mut result = zeroed();
             mk_err(self.table.GetSkeletalSummaryData.unwrap()(
                 action,
-                summary_type.as_raw(),
+                summary_type.as_ffi(),
                 &mut result,
             ))?;
             Ok(result)
@@ -293,7 +298,7 @@ impl<'a> VRInput<'a> {
                 let mut required_len: u32 = 0;
                 let err = self.table.GetSkeletalBoneDataCompressed.unwrap()(
                     action,
-                    motion_range.as_raw(),
+                    motion_range.as_ffi(),
                     buffer.as_mut_ptr() as _,
                     buffer.capacity() as _,
                     &mut required_len as _,
@@ -319,7 +324,7 @@ impl<'a> VRInput<'a> {
             mk_err(self.table.DecompressSkeletalBoneData.unwrap()(
                 compressed_buffer.as_ptr() as _,
                 compressed_buffer.len() as _,
-                transform_space.as_raw(),
+                transform_space.as_ffi(),
                 transform_array.as_mut_ptr(),
                 transform_array.len() as _,
             ))
@@ -347,6 +352,26 @@ impl<'a> VRInput<'a> {
         }
     }
 
+    /// [`trigger_haptic_vibration_action`](Self::trigger_haptic_vibration_action) with `Duration`s
+    pub fn trigger_haptic_vibration_action_duration(
+        self,
+        action: crate::VRActionHandle_t,
+        start_from_now: std::time::Duration,
+        duration: std::time::Duration,
+        frequency: f32,
+        amplitude: f32,
+        restrict_to_device: crate::VRInputValueHandle_t,
+    ) -> Result {
+        self.trigger_haptic_vibration_action(
+            action,
+            start_from_now.as_secs_f32(),
+            duration.as_secs_f32(),
+            frequency,
+            amplitude,
+            restrict_to_device,
+        )
+    }
+
     pub fn get_action_origins(
         self,
         action_set_handle: crate::VRActionSetHandle_t,
@@ -1,14 +1,29 @@
 extern crate core;
 macro_rules! c_like_enum {
-    ($name: ident as $ty: ty; $($value: ident = $expr: expr,)*) => {
-        #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    (
+        $name: ident as $ty: ty, ffi $ffi: ty;
+        $($value: ident = $expr: expr => $cpp: literal,)*
+        $(; alias $($alias: ident = $alias_expr: expr => $alias_cpp: literal,)*)?
+    ) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
         pub struct $name($ty);
-        #[allow(non_upper_case_globals)]
+        #[allow(non_upper_case_globals, clippy::unnecessary_cast)]
         impl $name {
-            $(pub const $value: $name = $name($expr);)*
+            $(pub const $value: $name = $name($expr as $ty);)*
+            $($(pub const $alias: $name = $name($alias_expr as $ty);)*)?
         }
 
         impl $name {
+            /// All values known by this version of OpenVR. aliases are not included.
+            pub const ALL: &'static [$name] = &[$($name::$value,)*];
+
+            // (value, rust name, cpp name). aliases are after all values.
+            const NAMES: &'static [($name, &'static str, &'static str)] = &[
+                $(($name::$value, stringify!($value), $cpp),)*
+                $($(($name::$alias, stringify!($alias), $alias_cpp),)*)?
+            ];
+
+            /// Returns the raw value. The type of raw value is same on all platforms.
             pub fn as_raw(self) -> $ty {
                 self.0
             }
@@ -16,6 +31,58 @@ macro_rules! c_like_enum {
             pub fn from_raw(raw: $ty) -> Self {
                 Self(raw)
             }
+
+            // the type of C enum depends on the platform (e.g. always signed on windows)
+            #[allow(dead_code, clippy::unnecessary_cast)]
+            pub(crate) fn as_ffi(self) -> $ffi {
+                self.0 as $ffi
+            }
+
+            #[allow(dead_code, clippy::unnecessary_cast)]
+            pub(crate) fn from_ffi(raw: $ffi) -> Self {
+                Self(raw as $ty)
+            }
+
+            /// Returns the name of the constant in rust if this is a known value.
+            pub fn name(self) -> Option<&'static str> {
+                Self::NAMES.iter().find(|x| x.0 == self).map(|x| x.1)
+            }
+
+            /// Returns the name of the value in openvr c++ header if this is a known value.
+            pub fn cpp_name(self) -> Option<&'static str> {
+                Self::NAMES.iter().find(|x| x.0 == self).map(|x| x.2)
+            }
+
+            fn from_name(name: &str) -> Option<Self> {
+                Self::NAMES
+                    .iter()
+                    .find(|x| x.1 == name || x.2 == name)
+                    .map(|x| x.0)
+            }
+        }
+
+        impl crate::internal::EnumBits for $name {
+            #[allow(clippy::unnecessary_cast)]
+            fn from_bits(bits: u32) -> Self {
+                Self(bits as $ty)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self.serde_name() {
+                    Some(name) => serializer.serialize_str(&name),
+                    None => ::serde::Serialize::serialize(&self.0, serializer),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(crate::EnumVisitor::<Self>::new(stringify!($name)))
+            }
         }
     };
 }
@@ -23,7 +90,7 @@ macro_rules! c_like_enum {
 macro_rules! bits_enum_display {
     ($name: ident = $all: expr; $($value: ident = $expr: expr,)*) => {
         impl ::core::fmt::Display for $name {
-            #[allow(unused_assignments)]
+            #[allow(unused_assignments, clippy::bad_bit_mask)]
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 let mut inner = self.0;
                 // shorthand for all.
@@ -50,16 +117,202 @@ macro_rules! bits_enum_display {
                     }
                     f.write_str(stringify!($value))?;
                     written = true;
-                    inner = inner & !$expr;
+                    inner &= !$expr;
                 })*
 
                 if inner != 0 {
                     // there's rest elements
-                    write!(f, " | {:#8x}", inner)?;
+                    if written {
+                        f.write_str(" | ")?;
+                    }
+                    write!(f, "{:#8x}", inner)?;
                 }
                 f.write_str(")")
             }
         }
+
+        impl $name {
+            /// Returns true if all bits are known flags
+            pub fn is_known(self) -> bool {
+                let known = 0 $(| $expr)*;
+                self.0 == $all || (self.0 & !known) == 0
+            }
+
+            #[allow(dead_code, clippy::bad_bit_mask)]
+            fn serde_name(self) -> Option<::std::borrow::Cow<'static, str>> {
+                if let Some(name) = self.name() {
+                    return Some(::std::borrow::Cow::Borrowed(name));
+                }
+                let mut inner = self.0;
+                let mut names = vec![];
+                $( if $expr != 0 && (inner & $expr) == $expr {
+                    names.push(stringify!($value));
+                    inner &= !$expr;
+                })*
+                // empty value without zero-valued name is serialized as a number
+                some_if!(::std::borrow::Cow::Owned(names.join(" | ")); if inner == 0 && !names.is_empty())
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = crate::ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let inner = crate::strip_enum_wrapper(s, stringify!($name));
+                if inner == "All" {
+                    return Ok(Self($all));
+                }
+                let mut value = 0;
+                for part in inner.split('|') {
+                    let part = part.trim();
+                    let flag = Self::from_name(part)
+                        .or_else(|| crate::parse_raw_enum_value(part).map(crate::internal::EnumBits::from_bits))
+                        .ok_or_else(|| crate::ParseEnumError::new(stringify!($name), s))?;
+                    value |= flag.0;
+                }
+                Ok(Self(value))
+            }
+        }
+    };
+}
+
+macro_rules! bits_enum_ops {
+    ($name: ident; $($value: ident = $expr: expr,)*) => {
+        impl $name {
+            /// Returns the value without any flags
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Returns the value with all flags known by this version of OpenVR
+            pub const fn all() -> Self {
+                Self(0 $(| $expr)*)
+            }
+
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns true if all flags in `other` are set in `self`
+            pub const fn contains(self, other: Self) -> bool {
+                (self.0 & other.0) == other.0
+            }
+
+            /// Returns true if any of flags in `other` are set in `self`
+            pub const fn intersects(self, other: Self) -> bool {
+                (self.0 & other.0) != 0
+            }
+
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+
+            pub fn toggle(&mut self, other: Self) {
+                self.0 ^= other.0;
+            }
+
+            pub fn set(&mut self, other: Self, value: bool) {
+                if value {
+                    self.insert(other)
+                } else {
+                    self.remove(other)
+                }
+            }
+
+            /// Iterates over known flags set in this value.
+            /// Bits not known by this version of OpenVR are returned as one value at the last.
+            pub fn iter(self) -> impl Iterator<Item = Self> {
+                let known = Self::ALL
+                    .iter()
+                    .copied()
+                    .filter(move |&flag| !flag.is_empty() && self.contains(flag));
+                let unknown = Self(self.0 & !Self::all().0);
+                known.chain(some_if!(unknown; if !unknown.is_empty()))
+            }
+        }
+
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl ::core::ops::BitXor for $name {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self) -> Self {
+                Self(self.0 ^ rhs.0)
+            }
+        }
+
+        impl ::core::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 & !rhs.0)
+            }
+        }
+
+        /// The complement of all bits, including bits not known by this version of OpenVR
+        impl ::core::ops::Not for $name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                Self(!self.0)
+            }
+        }
+
+        impl ::core::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl ::core::ops::BitAndAssign for $name {
+            fn bitand_assign(&mut self, rhs: Self) {
+                self.0 &= rhs.0;
+            }
+        }
+
+        impl ::core::ops::BitXorAssign for $name {
+            fn bitxor_assign(&mut self, rhs: Self) {
+                self.0 ^= rhs.0;
+            }
+        }
+
+        impl ::core::ops::SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 &= !rhs.0;
+            }
+        }
+
+        impl ::core::iter::FromIterator<$name> for $name {
+            fn from_iter<I: IntoIterator<Item = $name>>(iter: I) -> Self {
+                iter.into_iter().fold(Self::empty(), |a, b| a | b)
+            }
+        }
+
+        impl ::core::iter::Extend<$name> for $name {
+            fn extend<I: IntoIterator<Item = $name>>(&mut self, iter: I) {
+                for flag in iter {
+                    self.insert(flag);
+                }
+            }
+        }
     };
 }
 
@@ -67,45 +320,265 @@ macro_rules! simple_enum_display {
     ($name: ident; $($value: ident = $expr: expr,)*) => {
         impl ::core::fmt::Display for $name {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                $( if self.0 == $expr {
-                    f.write_str(concat!(stringify!($name), "(", stringify!($name), ")"))
-                } else )* {
-                    write!(f, "{}({})", stringify!($name), self.0)
+                match self.name() {
+                    Some(name) => write!(f, "{}({})", stringify!($name), name),
+                    None => write!(f, "{}({})", stringify!($name), self.0),
                 }
             }
         }
+
+        impl $name {
+            /// Returns true if this is a value known by this version of OpenVR
+            pub fn is_known(self) -> bool {
+                self.name().is_some()
+            }
+
+            #[allow(dead_code)]
+            fn serde_name(self) -> Option<::std::borrow::Cow<'static, str>> {
+                self.name().map(::std::borrow::Cow::Borrowed)
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = crate::ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let inner = crate::strip_enum_wrapper(s, stringify!($name));
+                Self::from_name(inner)
+                    .or_else(|| {
+                        crate::parse_raw_enum_value(inner).map(crate::internal::EnumBits::from_bits)
+                    })
+                    .ok_or_else(|| crate::ParseEnumError::new(stringify!($name), s))
+            }
+        }
     };
 }
 
 #[allow(unused_macros)]
 macro_rules! unsigned_bits_enum {
-    ($name: ident; $($value: ident = $expr: expr,)*) => {
-        c_like_enum!{$name as u32; $($value = $expr,)*}
-        bits_enum_display!{$name = u32::MAX; $($value = $expr,)*}
+    (
+        $name: ident as $ffi: ty;
+        $($value: ident = $expr: expr => $cpp: literal,)*
+        $(; alias $($alias: ident = $alias_expr: expr => $alias_cpp: literal,)*)?
+    ) => {
+        c_like_enum!{
+            $name as u32, ffi $ffi;
+            $($value = $expr => $cpp,)*
+            $(; alias $($alias = $alias_expr => $alias_cpp,)*)?
+        }
+        bits_enum_display!{$name = u32::MAX; $($value = $name::$value.0,)*}
+        bits_enum_ops!{$name; $($value = $name::$value.0,)*}
     };
 }
 
 #[allow(unused_macros)]
 macro_rules! signed_bits_enum {
-    ($name: ident; $($value: ident = $expr: expr,)*) => {
-        c_like_enum!{$name as i32; $($value = $expr,)*}
-        bits_enum_display!{$name = -1; $($value = $expr,)*}
+    (
+        $name: ident as $ffi: ty;
+        $($value: ident = $expr: expr => $cpp: literal,)*
+        $(; alias $($alias: ident = $alias_expr: expr => $alias_cpp: literal,)*)?
+    ) => {
+        c_like_enum!{
+            $name as i32, ffi $ffi;
+            $($value = $expr => $cpp,)*
+            $(; alias $($alias = $alias_expr => $alias_cpp,)*)?
+        }
+        bits_enum_display!{$name = -1; $($value = $name::$value.0,)*}
+        bits_enum_ops!{$name; $($value = $name::$value.0,)*}
     };
 }
 
 #[allow(unused_macros)]
 macro_rules! unsigned_enum {
-    ($name: ident; $($value: ident = $expr: expr,)*) => {
-        c_like_enum!{$name as u32; $($value = $expr,)*}
-        simple_enum_display!{$name; $($value = $expr,)*}
+    (
+        $name: ident as $ffi: ty;
+        $($value: ident = $expr: expr => $cpp: literal,)*
+        $(; alias $($alias: ident = $alias_expr: expr => $alias_cpp: literal,)*)?
+    ) => {
+        c_like_enum!{
+            $name as u32, ffi $ffi;
+            $($value = $expr => $cpp,)*
+            $(; alias $($alias = $alias_expr => $alias_cpp,)*)?
+        }
+        simple_enum_display!{$name; $($value = $name::$value.0,)*}
     };
 }
 
 #[allow(unused_macros)]
 macro_rules! signed_enum {
-    ($name: ident; $($value: ident = $expr: expr,)*) => {
-        c_like_enum!{$name as i32; $($value = $expr,)*}
-        simple_enum_display!{$name; $($value = $expr,)*}
+    (
+        $name: ident as $ffi: ty;
+        $($value: ident = $expr: expr => $cpp: literal,)*
+        $(; alias $($alias: ident = $alias_expr: expr => $alias_cpp: literal,)*)?
+    ) => {
+        c_like_enum!{
+            $name as i32, ffi $ffi;
+            $($value = $expr => $cpp,)*
+            $(; alias $($alias = $alias_expr => $alias_cpp,)*)?
+        }
+        simple_enum_display!{$name; $($value = $name::$value.0,)*}
+    };
+}
+
+macro_rules! exhaustive_enum {
+    ($name: ident as $ty: ty; $($variant: ident = $value: ident,)*) => {
+        #[doc = concat!("Exhaustive version of [`", stringify!($name), "`](crate::", stringify!($name), ")")]
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+        #[allow(clippy::upper_case_acronyms)]
+        pub enum $name {
+            $($variant,)*
+            /// The value not known by this version of OpenVR
+            Unknown($ty),
+        }
+
+        impl From<crate::enums::$name> for $name {
+            fn from(value: crate::enums::$name) -> Self {
+                match value {
+                    $(crate::enums::$name::$value => $name::$variant,)*
+                    _ => $name::Unknown(value.as_raw()),
+                }
+            }
+        }
+
+        impl From<$name> for crate::enums::$name {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => crate::enums::$name::$value,)*
+                    $name::Unknown(raw) => crate::enums::$name::from_raw(raw),
+                }
+            }
+        }
+
+        impl crate::enums::$name {
+            /// Converts to the enum which can be matched exhaustively.
+            pub fn to_exhaustive(self) -> $name {
+                self.into()
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&crate::enums::$name::from(*self), f)
+            }
+        }
+    };
+}
+
+macro_rules! handle_type {
+    ($name: ident($ty: ty)) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord, Default)]
+        #[repr(transparent)]
+        pub struct $name(pub $ty);
+
+        impl $name {
+            pub const fn as_raw(self) -> $ty {
+                self.0
+            }
+
+            pub const fn from_raw(raw: $ty) -> Self {
+                Self(raw)
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(raw: $ty) -> Self {
+                Self(raw)
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+macro_rules! mirror_field {
+    (from_ffi copy $ty: ty, $value: expr) => {
+        $value
+    };
+    (to_ffi copy $value: expr) => {
+        $value
+    };
+    (from_ffi enum $ty: ty, $value: expr) => {
+        <$ty>::from_ffi($value)
+    };
+    (to_ffi enum $value: expr) => {
+        $value.as_ffi()
+    };
+    (from_ffi raw $ty: ty, $value: expr) => {
+        <$ty>::from_raw($value)
+    };
+    (to_ffi raw $value: expr) => {
+        $value.as_raw()
+    };
+    (from_ffi into $ty: ty, $value: expr) => {
+        $value.into()
+    };
+    (to_ffi into $value: expr) => {
+        $value.into()
+    };
+    (from_ffi map_into $ty: ty, $value: expr) => {
+        $value.map(Into::into)
+    };
+    (to_ffi map_into $value: expr) => {
+        $value.map(Into::into)
+    };
+}
+
+macro_rules! mirror_struct {
+    ($name: ident as $ffi: ty {
+        $($field: ident: $ty: ty = $ffi_field: ident $conv: ident,)*
+    }) => {
+        #[doc = concat!("Rust version of [`", stringify!($ffi), "`]")]
+        #[derive(Copy, Clone, PartialEq, Debug)]
+        pub struct $name {
+            $(pub $field: $ty,)*
+        }
+
+        impl From<$ffi> for $name {
+            fn from(raw: $ffi) -> Self {
+                Self {
+                    $($field: mirror_field!(from_ffi $conv $ty, raw.$ffi_field),)*
+                }
+            }
+        }
+
+        impl From<$name> for $ffi {
+            fn from(value: $name) -> Self {
+                Self {
+                    $($ffi_field: mirror_field!(to_ffi $conv value.$field),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! low_level_interface {
+    ($name: ident as $table: ident, $version: ident) => {
+        #[doc = concat!("Low-level wrapper of `", stringify!($name), "`")]
+        #[derive(Copy, Clone)]
+        pub struct $name<'a> {
+            table: &'a ::openvr_sys::$table,
+        }
+
+        impl<'a> $name<'a> {
+            pub(crate) fn new(table: &'a ::openvr_sys::$table) -> Self {
+                Self { table }
+            }
+        }
+
+        impl crate::Sealed for $name<'_> {}
+
+        impl<'a> Interface<'a> for $name<'a> {
+            const VERSION: &'static [u8] = ::openvr_sys::$version;
+            type FnTable = ::openvr_sys::$table;
+
+            fn from_table(table: &'a Self::FnTable) -> Self {
+                Self::new(table)
+            }
+        }
     };
 }
 
@@ -115,7 +588,7 @@ macro_rules! return_err {
     };
     ($err_value: expr, $($ty: ident)::+, $success: ident) => {
         {
-            let err = $($ty)::+::from_raw($err_value);
+            let err = $($ty)::+::from_ffi($err_value);
             if !matches!(err, $($ty)::+::$success) {
                 Err(err)
             } else {
@@ -169,6 +642,27 @@ macro_rules! cstr {
 
 mod internal {
     pub trait Sealed {}
+
+    pub trait EnumBits {
+        fn from_bits(bits: u32) -> Self;
+    }
+}
+
+/// returns null for empty slice since OpenVR may write to non-null buffer
+fn slice_ptr<T>(slice: &[T]) -> *const T {
+    if slice.is_empty() {
+        std::ptr::null()
+    } else {
+        slice.as_ptr()
+    }
+}
+
+fn slice_mut_ptr<T>(slice: &mut [T]) -> *mut T {
+    if slice.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        slice.as_mut_ptr()
+    }
 }
 
 #[inline(always)]
@@ -195,6 +689,47 @@ pub use input::VRInput;
 pub mod applications;
 pub use applications::VRApplications;
 
+pub mod math;
+
+pub mod space;
+pub use space::Transform;
+
+pub mod universe;
+pub use universe::UniverseConverter;
+
+pub mod pose;
+pub use pose::DevicePose;
+
+pub mod pose_history;
+pub use pose_history::PoseHistory;
+
+pub mod pose_filter;
+pub use pose_filter::PoseFilters;
+
+pub mod frame_clock;
+pub use frame_clock::FrameClock;
+
+pub mod devices;
+pub use devices::DeviceRegistry;
+
+pub mod property_cache;
+pub use property_cache::PropertyCache;
+
+pub mod battery;
+pub use battery::BatteryMonitor;
+
+pub mod firmware;
+pub use firmware::FirmwareUpdater;
+
+pub mod controller;
+pub use controller::ControllerState;
+
+pub mod haptics;
+pub use haptics::HapticScheduler;
+
+pub mod hidden_area;
+pub use hidden_area::HiddenAreaMesh;
+
 pub mod structs;
 pub use structs::*;
 
@@ -203,9 +738,172 @@ pub mod enums {
 }
 pub use enums::*;
 
+/// Typed handles and Rust version of OpenVR structs.
+///
+/// Handles are newtypes of raw integer typedefs like `TrackedDeviceIndex_t`. They are used by
+/// struct fields, the helper modules and [`low_level`]; the inner value is passed to the other wrappers.
+/// Structs use Rust-style field names and can be converted from / to `openvr_sys` structs with `From`.
+pub mod types {
+    use crate::enums::*;
+    include!(concat!(env!("OUT_DIR"), "/types.rs"));
+}
+pub use types::*;
+
+/// Low-level wrappers of all interface methods, generated from `openvr_api.json`.
+///
+/// Buffer and size pairs are slices, out parameters are return values and
+/// error enums are `Result`. Methods which take raw pointers are `unsafe`.
+/// Get the wrapper with [`VRContext::low_level`] or `low_level()` of high-level wrappers.
+#[allow(
+    clippy::too_many_arguments,
+    clippy::type_complexity,
+    clippy::unnecessary_cast
+)]
+pub mod low_level {
+    /// OpenVR interface which can be get by [`VRContext::low_level`](crate::VRContext::low_level)
+    pub trait Interface<'a>: crate::Sealed + Sized {
+        #[doc(hidden)]
+        const VERSION: &'static [u8];
+        #[doc(hidden)]
+        type FnTable: 'static;
+        #[doc(hidden)]
+        fn from_table(table: &'a Self::FnTable) -> Self;
+    }
+
+    /// The error of methods which fill the buffer, with the size the buffer needs.
+    ///
+    /// Retry with a buffer of `required` bytes if `error` is `BufferTooSmall`.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct BufferError<E> {
+        pub error: E,
+        pub required: u32,
+    }
+
+    impl<E: std::fmt::Display> std::fmt::Display for BufferError<E> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} (required size: {})", self.error, self.required)
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/low_level.rs"));
+}
+
+/// Typed keys of tracked device properties, like `Prop_SerialNumber_String` as `SERIAL_NUMBER`.
+///
+/// If there are properties with the same name and different types, type is kept in the name
+/// like `HARDWARE_REVISION_STRING` and `HARDWARE_REVISION_UINT64`.
+pub mod props {
+    use crate::system::{AnyProperty, Property};
+    include!(concat!(env!("OUT_DIR"), "/properties.rs"));
+}
+
+/// Constants defined in OpenVR, like `k_unMaxTrackedDeviceCount` as `MAX_TRACKED_DEVICE_COUNT`.
+#[allow(clippy::unnecessary_cast)]
+pub mod consts {
+    include!(concat!(env!("OUT_DIR"), "/consts.rs"));
+}
+
+/// Rust enums of OpenVR enums, which can be matched exhaustively.
+///
+/// Values not known by this version of OpenVR are represented as `Unknown(raw)`.
+/// If the OpenVR enum have value named `Unknown`, the value is named `UnknownValue`.
+pub mod exhaustive {
+    include!(concat!(env!("OUT_DIR"), "/exhaustive.rs"));
+}
+
+/// The error returned when parsing name of OpenVR enum failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    enum_name: &'static str,
+    input: String,
+}
+
+impl ParseEnumError {
+    fn new(enum_name: &'static str, input: &str) -> Self {
+        Self {
+            enum_name,
+            input: input.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown {} value: {:?}", self.enum_name, self.input)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
+/// removes `EnumName(` and `)` of Display format if exists
+fn strip_enum_wrapper<'a>(s: &'a str, enum_name: &str) -> &'a str {
+    let s = s.trim();
+    s.strip_prefix(enum_name)
+        .and_then(|x| x.strip_prefix('('))
+        .and_then(|x| x.strip_suffix(')'))
+        .map(str::trim)
+        .unwrap_or(s)
+}
+
+/// parses decimal or `0x` prefixed hexadecimal value as bits of 32-bit enum value
+fn parse_raw_enum_value(s: &str) -> Option<u32> {
+    if let Some(hex) = s.strip_prefix("0x") {
+        return u32::from_str_radix(hex, 16).ok();
+    }
+    enum_bits_from_i64(s.parse().ok()?)
+}
+
+fn enum_bits_from_i64(value: i64) -> Option<u32> {
+    some_if!(value as u32; if (i32::MIN as i64..=u32::MAX as i64).contains(&value))
+}
+
+#[cfg(feature = "serde")]
+struct EnumVisitor<T> {
+    enum_name: &'static str,
+    _phantom: PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> EnumVisitor<T> {
+    fn new(enum_name: &'static str) -> Self {
+        Self {
+            enum_name,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::de::Visitor<'de> for EnumVisitor<T>
+where
+    T: std::str::FromStr<Err = ParseEnumError> + internal::EnumBits,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "name or raw value of {}", self.enum_name)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<T, E> {
+        enum_bits_from_i64(v)
+            .map(T::from_bits)
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<T, E> {
+        u32::try_from(v)
+            .map(T::from_bits)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+    }
+}
+
 pub fn init(app_type: ApplicationType) -> Result<VRContext, InitError> {
     let mut err: openvr_sys::EVRInitError = 0;
-    let token = unsafe { openvr_sys::VR_InitInternal(&mut err, app_type.as_raw()) };
+    let token = unsafe { openvr_sys::VR_InitInternal(&mut err, app_type.as_ffi()) };
     return_err!(err, InitError)?;
 
     let system = VRContext::new(token);
@@ -238,13 +936,13 @@ pub unsafe fn get_function_table<T>(pch_interface_version: &[u8]) -> Result<NonN
         table_len.extend_from_slice(b"FnTable:");
         table_len.extend_from_slice(pch_interface_version);
         let ptr = openvr_sys::VR_GetGenericInterface(table_len.as_ptr().cast(), &mut err);
-        NonNull::new(ptr as *mut T).ok_or(InitError::from_raw(err))
+        NonNull::new(ptr as *mut T).ok_or(InitError::from_ffi(err))
     }
 }
 
 macro_rules! interface_writer {
     (fn $fn_name: ident -> $wrapper: ident from $name_ref: ident) => {
-        pub fn $fn_name(&self) -> Result<$wrapper, InitError> {
+        pub fn $fn_name(&self) -> Result<$wrapper<'_>, InitError> {
             unsafe {
                 let ptr = self
                     .$fn_name
@@ -272,6 +970,16 @@ impl VRContext {
     interface_writer!(fn input -> VRInput from IVRInput_Version);
     interface_writer!(fn application -> VRApplications from IVRApplications_Version);
 
+    /// Gets low-level wrapper of any interface like `ctx.low_level::<low_level::IVRCompositor>()`.
+    ///
+    /// The function table is not cached so keep the returned wrapper if you call many times.
+    pub fn low_level<'a, T: low_level::Interface<'a>>(&'a self) -> Result<T, InitError> {
+        unsafe {
+            let ptr = get_function_table::<T::FnTable>(T::VERSION)?;
+            Ok(T::from_table(&*ptr.as_ptr()))
+        }
+    }
+
     pub fn shutdown(self) {
         // drop does
     }
@@ -284,3 +992,282 @@ impl Drop for VRContext {
         }
     }
 }
+
+#[cfg(test)]
+mod enum_tests {
+    use super::*;
+
+    #[test]
+    fn simple_enum_names() {
+        assert_eq!(TrackedDeviceClass::Controller.name(), Some("Controller"));
+        assert_eq!(
+            TrackedDeviceClass::Controller.cpp_name(),
+            Some("TrackedDeviceClass_Controller")
+        );
+        assert_eq!(TrackedDeviceClass::from_raw(1000).name(), None);
+        assert!(!TrackedDeviceClass::from_raw(1000).is_known());
+        assert_eq!(ButtonId::IndexControllerA.name(), Some("Grip"));
+        assert_eq!(
+            ButtonId::IndexControllerA.cpp_name(),
+            Some("k_EButton_Grip")
+        );
+        assert_eq!(
+            ButtonId::ALL
+                .iter()
+                .filter(|&&x| x == ButtonId::Grip)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn exhaustive_conversion() {
+        assert_eq!(
+            TrackedDeviceClass::HMD.to_exhaustive(),
+            exhaustive::TrackedDeviceClass::HMD
+        );
+        assert_eq!(
+            ButtonId::IndexControllerA.to_exhaustive(),
+            exhaustive::ButtonId::Grip
+        );
+        assert_eq!(
+            InitError::Unknown.to_exhaustive(),
+            exhaustive::InitError::UnknownValue
+        );
+        let unknown = TrackedDeviceClass::from_raw(1000);
+        assert_eq!(
+            unknown.to_exhaustive(),
+            exhaustive::TrackedDeviceClass::Unknown(1000)
+        );
+        for &value in EventType::ALL {
+            assert_eq!(EventType::from(value.to_exhaustive()), value);
+        }
+        assert_eq!(TrackedDeviceClass::from(unknown.to_exhaustive()), unknown);
+    }
+
+    #[test]
+    fn raw_type_is_platform_independent() {
+        // signedness is decided by values, not by platform
+        let _: u32 = Eye::Left.as_raw();
+        let _: u32 = OverlayFlags::Panorama.as_raw();
+        let _: i32 = TextureType::Invalid.as_raw();
+    }
+
+    #[test]
+    fn simple_enum_from_str() {
+        for &value in TrackedDeviceClass::ALL {
+            assert_eq!(value.name().unwrap().parse(), Ok(value));
+            assert_eq!(value.cpp_name().unwrap().parse(), Ok(value));
+            assert_eq!(value.to_string().parse(), Ok(value));
+        }
+        let unknown = TrackedDeviceClass::from_raw(1000);
+        assert_eq!(unknown.to_string().parse(), Ok(unknown));
+        assert_eq!("IndexControllerA".parse(), Ok(ButtonId::Grip));
+        assert!("NotAClass".parse::<TrackedDeviceClass>().is_err());
+    }
+
+    #[test]
+    fn bits_enum_from_str() {
+        let flags = OverlayFlags::from_raw(
+            OverlayFlags::NoDashboardTab.as_raw() | OverlayFlags::SendVRSmoothScrollEvents.as_raw(),
+        );
+        assert!(flags.is_known());
+        assert_eq!(flags.to_string().parse(), Ok(flags));
+        assert_eq!(
+            "NoDashboardTab | SendVRSmoothScrollEvents".parse(),
+            Ok(flags)
+        );
+    }
+
+    #[test]
+    fn bits_enum_ops() {
+        let mut flags = OverlayFlags::NoDashboardTab | OverlayFlags::SendVRSmoothScrollEvents;
+        assert!(flags.contains(OverlayFlags::NoDashboardTab));
+        assert!(!flags.contains(OverlayFlags::Panorama));
+        flags.insert(OverlayFlags::Panorama);
+        flags.remove(OverlayFlags::NoDashboardTab);
+        assert_eq!(
+            flags.iter().collect::<Vec<_>>(),
+            vec![
+                OverlayFlags::Panorama,
+                OverlayFlags::SendVRSmoothScrollEvents
+            ]
+        );
+        assert_eq!(flags.iter().collect::<OverlayFlags>(), flags);
+        assert!((flags & !flags).is_empty());
+        assert!(OverlayFlags::all().contains(flags));
+        assert!(OverlayFlags::empty().is_empty());
+
+        let unknown = OverlayFlags::from_raw(1 << 30);
+        assert_eq!((flags | unknown).iter().last(), Some(unknown));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_by_name() {
+        let json = serde_json::to_string(&TrackedDeviceClass::HMD).unwrap();
+        assert_eq!(json, "\"HMD\"");
+        assert_eq!(
+            serde_json::from_str::<TrackedDeviceClass>(&json).unwrap(),
+            TrackedDeviceClass::HMD
+        );
+        let unknown = TrackedDeviceClass::from_raw(1000);
+        let json = serde_json::to_string(&unknown).unwrap();
+        assert_eq!(json, "1000");
+        assert_eq!(
+            serde_json::from_str::<TrackedDeviceClass>(&json).unwrap(),
+            unknown
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_bits_round_trip() {
+        let multi = OverlayFlags::NoDashboardTab | OverlayFlags::SendVRTouchpadEvents;
+        let unknown = OverlayFlags::NoDashboardTab | OverlayFlags::from_raw(1 << 30);
+        for flags in [
+            OverlayFlags::empty(),
+            OverlayFlags::NoDashboardTab,
+            multi,
+            unknown,
+        ] {
+            let json = serde_json::to_string(&flags).unwrap();
+            assert_eq!(
+                serde_json::from_str::<OverlayFlags>(&json).unwrap(),
+                flags,
+                "{}",
+                json
+            );
+        }
+        assert_eq!(serde_json::to_string(&OverlayFlags::empty()).unwrap(), "0");
+    }
+
+    #[test]
+    fn display_bits_round_trip() {
+        let unknown = OverlayFlags::from_raw(1 << 30);
+        for flags in [unknown, OverlayFlags::NoDashboardTab | unknown] {
+            assert_eq!(flags.to_string().parse(), Ok(flags), "{}", flags);
+        }
+        assert!(!unknown.to_string().contains('|'));
+    }
+}
+
+#[cfg(test)]
+mod types_tests {
+    use super::*;
+
+    #[test]
+    fn struct_round_trip() {
+        let raw = openvr_sys::TrackedDevicePose_t {
+            mDeviceToAbsoluteTracking: HmdMatrix34_t {
+                m: [
+                    [1.0, 0.0, 0.0, 2.0],
+                    [0.0, 1.0, 0.0, 3.0],
+                    [0.0, 0.0, 1.0, 4.0],
+                ],
+            },
+            vVelocity: HmdVector3_t { v: [1.0, 2.0, 3.0] },
+            vAngularVelocity: HmdVector3_t { v: [0.0, 0.5, 0.0] },
+            eTrackingResult: TrackingResult::RunningOK.as_ffi(),
+            bPoseIsValid: true,
+            bDeviceIsConnected: true,
+        };
+        let pose = TrackedDevicePose::from(raw);
+        assert_eq!(pose.velocity.v, [1.0, 2.0, 3.0]);
+        assert_eq!(pose.device_to_absolute_tracking.m[1][3], 3.0);
+        assert_eq!(pose.tracking_result, TrackingResult::RunningOK);
+        assert!(pose.pose_is_valid);
+        let back = openvr_sys::TrackedDevicePose_t::from(pose);
+        assert_eq!(TrackedDevicePose::from(back), pose);
+    }
+
+    #[test]
+    fn consts() {
+        assert_eq!(consts::MAX_TRACKED_DEVICE_COUNT, 64);
+        assert_eq!(
+            consts::TRACKED_DEVICE_INDEX_INVALID,
+            TrackedDeviceIndex(openvr_sys::k_unTrackedDeviceIndexInvalid as u32)
+        );
+        assert_eq!(
+            consts::INVALID_PROPERTY_CONTAINER,
+            PropertyContainerHandle(0)
+        );
+        assert_eq!(
+            consts::IVR_SYSTEM_VERSION.to_bytes_with_nul(),
+            openvr_sys::IVRSystem_Version
+        );
+    }
+
+    #[test]
+    fn props() {
+        let serial: crate::system::Property<String> = props::SERIAL_NUMBER;
+        assert_eq!(serial.prop(), TrackedDeviceProperty::SerialNumberString);
+        assert_eq!(
+            props::HARDWARE_REVISION_UINT64.prop(),
+            TrackedDeviceProperty::HardwareRevisionUint64
+        );
+        let rates: crate::system::Property<Vec<f32>> = props::DISPLAY_AVAILABLE_FRAME_RATES;
+        assert_eq!(
+            rates.prop(),
+            TrackedDeviceProperty::DisplayAvailableFrameRatesFloatArray
+        );
+        assert!(props::ALL
+            .iter()
+            .any(|x| x.prop() == TrackedDeviceProperty::SerialNumberString));
+    }
+}
+
+#[cfg(test)]
+mod low_level_tests {
+    use super::*;
+    use low_level::Interface;
+
+    unsafe extern "C" fn get_string_property(
+        _: openvr_sys::TrackedDeviceIndex_t,
+        _: openvr_sys::ETrackedDeviceProperty,
+        _: *mut std::os::raw::c_char,
+        buffer_size: u32,
+        error: *mut openvr_sys::ETrackedPropertyError,
+    ) -> u32 {
+        *error = match buffer_size {
+            0..=5 => TrackedPropertyError::BufferTooSmall,
+            _ => TrackedPropertyError::Success,
+        }
+        .as_ffi();
+        6
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let mut table: openvr_sys::VR_IVRSystem_FnTable = unsafe { std::mem::zeroed() };
+        table.GetStringTrackedDeviceProperty = Some(get_string_property);
+        let system = low_level::IVRSystem::from_table(&table);
+        let serial = TrackedDeviceProperty::SerialNumberString;
+        let device = TrackedDeviceIndex(0);
+
+        assert_eq!(
+            system.get_string_tracked_device_property(device, serial, &mut []),
+            Err(low_level::BufferError {
+                error: TrackedPropertyError::BufferTooSmall,
+                required: 6,
+            })
+        );
+        assert_eq!(
+            system.get_string_tracked_device_property(device, serial, &mut [0; 6]),
+            Ok(6)
+        );
+    }
+
+    #[test]
+    fn method_names() {
+        // digits and acronyms are kept in one word
+        let _ = low_level::IVRSystem::get_d3d9_adapter_index;
+        let _ = low_level::IVRSystem::get_dxgi_output_info;
+        let _ = low_level::IVRSystem::get_uint64_tracked_device_property;
+        let _ = low_level::IVRCompositor::release_mirror_texture_d3d11;
+        let _ = low_level::IVRInput::open_binding_ui;
+        let _ = low_level::IVRApplications::get_current_scene_process_id;
+        let _ = consts::HMD_VECTOR2_PROPERTY_TAG;
+        let _ = props::AXIS0_TYPE;
+    }
+}
@@ -0,0 +1,427 @@
+//! The math helpers for OpenVR structs.
+//!
+//! With `mint`, `glam` or `nalgebra` feature, `From` is implemented in both directions between
+//! the mirror types ([`HmdMatrix34`](crate::HmdMatrix34), [`HmdMatrix44`](crate::HmdMatrix44),
+//! [`HmdVector3`](crate::HmdVector3), [`HmdQuaternionf`](crate::HmdQuaternionf) and
+//! [`HmdQuad`](crate::HmdQuad)) and the types of the library.
+//! The raw structs can be converted through the mirror types.
+
+use crate::{HmdMatrix34_t, HmdQuaternionf_t, HmdVector3_t};
+
+/// The transform which does nothing
+pub fn identity() -> HmdMatrix34_t {
+    HmdMatrix34_t {
+        m: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ],
+    }
+}
+
+/// Composes transforms. `b` is applied first like multiplication of matrices.
+pub fn multiply(a: &HmdMatrix34_t, b: &HmdMatrix34_t) -> HmdMatrix34_t {
+    let mut result = HmdMatrix34_t { m: [[0.0; 4]; 3] };
+    for row in 0..3 {
+        for col in 0..4 {
+            let mut value = (0..3).map(|i| a.m[row][i] * b.m[i][col]).sum::<f32>();
+            if col == 3 {
+                value += a.m[row][3];
+            }
+            result.m[row][col] = value;
+        }
+    }
+    result
+}
+
+/// The inverse of transform which consists of rotation and translation only.
+pub fn inverse_rigid(matrix: &HmdMatrix34_t) -> HmdMatrix34_t {
+    let m = &matrix.m;
+    // transpose the rotation and rotate back the translation
+    HmdMatrix34_t {
+        m: [0, 1, 2].map(|row| {
+            let translation = -(0..3).map(|i| m[i][row] * m[i][3]).sum::<f32>();
+            [m[0][row], m[1][row], m[2][row], translation]
+        }),
+    }
+}
+
+/// Transforms the point with rotation, scale and translation.
+pub fn transform_point(matrix: &HmdMatrix34_t, point: HmdVector3_t) -> HmdVector3_t {
+    let mut result = transform_direction(matrix, point);
+    for (row, value) in result.v.iter_mut().enumerate() {
+        *value += matrix.m[row][3];
+    }
+    result
+}
+
+/// Transforms the direction with rotation and scale. Translation is not applied.
+pub fn transform_direction(matrix: &HmdMatrix34_t, direction: HmdVector3_t) -> HmdVector3_t {
+    HmdVector3_t {
+        v: [0, 1, 2].map(|row| (0..3).map(|i| matrix.m[row][i] * direction.v[i]).sum()),
+    }
+}
+
+/// The translation part of the transform
+pub fn translation(matrix: &HmdMatrix34_t) -> HmdVector3_t {
+    HmdVector3_t {
+        v: [matrix.m[0][3], matrix.m[1][3], matrix.m[2][3]],
+    }
+}
+
+/// Extracts the rotation part of the rigid transform as an unit quaternion.
+pub fn rotation(matrix: &HmdMatrix34_t) -> HmdQuaternionf_t {
+    let m = &matrix.m;
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        HmdQuaternionf_t {
+            w: 0.25 * s,
+            x: (m[2][1] - m[1][2]) / s,
+            y: (m[0][2] - m[2][0]) / s,
+            z: (m[1][0] - m[0][1]) / s,
+        }
+    } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+        let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+        HmdQuaternionf_t {
+            w: (m[2][1] - m[1][2]) / s,
+            x: 0.25 * s,
+            y: (m[0][1] + m[1][0]) / s,
+            z: (m[0][2] + m[2][0]) / s,
+        }
+    } else if m[1][1] > m[2][2] {
+        let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+        HmdQuaternionf_t {
+            w: (m[0][2] - m[2][0]) / s,
+            x: (m[0][1] + m[1][0]) / s,
+            y: 0.25 * s,
+            z: (m[1][2] + m[2][1]) / s,
+        }
+    } else {
+        let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+        HmdQuaternionf_t {
+            w: (m[1][0] - m[0][1]) / s,
+            x: (m[0][2] + m[2][0]) / s,
+            y: (m[1][2] + m[2][1]) / s,
+            z: 0.25 * s,
+        }
+    }
+}
+
+/// Creates the rigid transform from the translation and the unit quaternion.
+pub fn from_translation_rotation(
+    translation: HmdVector3_t,
+    rotation: HmdQuaternionf_t,
+) -> HmdMatrix34_t {
+    let HmdQuaternionf_t { w, x, y, z } = rotation;
+    let [px, py, pz] = translation.v;
+    HmdMatrix34_t {
+        m: [
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+                px,
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+                py,
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+                pz,
+            ],
+        ],
+    }
+}
+
+/// Linear interpolation. `t` is `0.0` for `a` and `1.0` for `b`.
+pub fn lerp(a: HmdVector3_t, b: HmdVector3_t, t: f32) -> HmdVector3_t {
+    HmdVector3_t {
+        v: [0, 1, 2].map(|i| a.v[i] + (b.v[i] - a.v[i]) * t),
+    }
+}
+
+/// Spherical linear interpolation of unit quaternions. `t` is `0.0` for `a` and `1.0` for `b`.
+pub fn slerp(a: HmdQuaternionf_t, b: HmdQuaternionf_t, t: f32) -> HmdQuaternionf_t {
+    let a = [a.w, a.x, a.y, a.z];
+    let mut b = [b.w, b.x, b.y, b.z];
+    let mut dot: f32 = (0..4).map(|i| a[i] * b[i]).sum();
+    // take the shorter path
+    if dot < 0.0 {
+        b = b.map(|x| -x);
+        dot = -dot;
+    }
+    let (wa, wb) = if dot > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let theta = dot.acos();
+        let sin = theta.sin();
+        (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+    };
+    let q = [0, 1, 2, 3].map(|i| a[i] * wa + b[i] * wb);
+    let len = q.iter().map(|x| x * x).sum::<f32>().sqrt();
+    HmdQuaternionf_t {
+        w: q[0] / len,
+        x: q[1] / len,
+        y: q[2] / len,
+        z: q[3] / len,
+    }
+}
+
+/// Composes rotations. `b` is applied first.
+pub fn multiply_quaternion(a: HmdQuaternionf_t, b: HmdQuaternionf_t) -> HmdQuaternionf_t {
+    HmdQuaternionf_t {
+        w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+        y: a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+        z: a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+    }
+}
+
+/// The inverse rotation of the unit quaternion
+pub fn conjugate(q: HmdQuaternionf_t) -> HmdQuaternionf_t {
+    HmdQuaternionf_t {
+        w: q.w,
+        x: -q.x,
+        y: -q.y,
+        z: -q.z,
+    }
+}
+
+/// Implements `From` in both directions
+#[allow(unused_macros)]
+macro_rules! from_both {
+    ($a: ty, $b: ty, |$x: ident| $to: expr, |$y: ident| $from: expr $(,)?) => {
+        impl From<$a> for $b {
+            fn from($x: $a) -> $b {
+                $to
+            }
+        }
+
+        impl From<$b> for $a {
+            fn from($y: $b) -> $a {
+                $from
+            }
+        }
+    };
+}
+
+#[cfg(feature = "mint")]
+mod mint_impl {
+    use crate::types::{HmdMatrix34, HmdMatrix44, HmdQuad, HmdQuaternionf, HmdVector3};
+    use mint::{Quaternion, RowMatrix3x4, RowMatrix4, Vector3};
+
+    from_both!(HmdMatrix34, RowMatrix3x4<f32>, |x| x.m.into(), |x| {
+        HmdMatrix34 { m: x.into() }
+    });
+    from_both!(HmdMatrix44, RowMatrix4<f32>, |x| x.m.into(), |x| {
+        HmdMatrix44 { m: x.into() }
+    });
+    from_both!(HmdVector3, Vector3<f32>, |x| x.v.into(), |x| HmdVector3 {
+        v: x.into()
+    });
+    from_both!(
+        HmdQuaternionf,
+        Quaternion<f32>,
+        |x| Quaternion {
+            v: Vector3 {
+                x: x.x,
+                y: x.y,
+                z: x.z
+            },
+            s: x.w,
+        },
+        |x| HmdQuaternionf {
+            w: x.s,
+            x: x.v.x,
+            y: x.v.y,
+            z: x.v.z,
+        },
+    );
+    from_both!(
+        HmdQuad,
+        [Vector3<f32>; 4],
+        |x| x.corners.map(Into::into),
+        |x| HmdQuad {
+            corners: x.map(Into::into)
+        },
+    );
+}
+
+#[cfg(feature = "glam")]
+mod glam_impl {
+    use crate::types::{HmdMatrix34, HmdMatrix44, HmdQuad, HmdQuaternionf, HmdVector3};
+    use glam::{Affine3A, Mat4, Quat, Vec3, Vec3A};
+
+    from_both!(
+        HmdMatrix34,
+        Affine3A,
+        |x| {
+            let column = |i: usize| Vec3A::new(x.m[0][i], x.m[1][i], x.m[2][i]);
+            Affine3A::from_cols(column(0), column(1), column(2), column(3))
+        },
+        |x| {
+            let columns = [
+                x.matrix3.x_axis,
+                x.matrix3.y_axis,
+                x.matrix3.z_axis,
+                x.translation,
+            ];
+            HmdMatrix34 {
+                m: [0, 1, 2].map(|row| columns.map(|column| column[row])),
+            }
+        },
+    );
+    // glam is column major
+    from_both!(
+        HmdMatrix44,
+        Mat4,
+        |x| Mat4::from_cols_array_2d(&x.m).transpose(),
+        |x| HmdMatrix44 {
+            m: x.transpose().to_cols_array_2d()
+        },
+    );
+    from_both!(HmdVector3, Vec3, |x| Vec3::from_array(x.v), |x| {
+        HmdVector3 { v: x.to_array() }
+    });
+    from_both!(
+        HmdQuaternionf,
+        Quat,
+        |x| Quat::from_xyzw(x.x, x.y, x.z, x.w),
+        |x| HmdQuaternionf {
+            w: x.w,
+            x: x.x,
+            y: x.y,
+            z: x.z,
+        },
+    );
+    from_both!(HmdQuad, [Vec3; 4], |x| x.corners.map(Into::into), |x| {
+        HmdQuad {
+            corners: x.map(Into::into),
+        }
+    },);
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impl {
+    use crate::types::{HmdMatrix34, HmdMatrix44, HmdQuad, HmdQuaternionf, HmdVector3};
+    use nalgebra::{Matrix3x4, Matrix4, Quaternion, Vector3};
+
+    from_both!(
+        HmdMatrix34,
+        Matrix3x4<f32>,
+        |x| Matrix3x4::from_fn(|row, col| x.m[row][col]),
+        |x| HmdMatrix34 {
+            m: [0, 1, 2].map(|row| [0, 1, 2, 3].map(|col| x[(row, col)])),
+        },
+    );
+    from_both!(
+        HmdMatrix44,
+        Matrix4<f32>,
+        |x| Matrix4::from_fn(|row, col| x.m[row][col]),
+        |x| HmdMatrix44 {
+            m: [0, 1, 2, 3].map(|row| [0, 1, 2, 3].map(|col| x[(row, col)])),
+        },
+    );
+    from_both!(HmdVector3, Vector3<f32>, |x| Vector3::from(x.v), |x| {
+        HmdVector3 { v: x.into() }
+    });
+    from_both!(
+        HmdQuaternionf,
+        Quaternion<f32>,
+        |x| Quaternion::new(x.w, x.x, x.y, x.z),
+        |x| HmdQuaternionf {
+            w: x.w,
+            x: x.i,
+            y: x.j,
+            z: x.k,
+        },
+    );
+    from_both!(
+        HmdQuad,
+        [Vector3<f32>; 4],
+        |x| x.corners.map(Into::into),
+        |x| HmdQuad {
+            corners: x.map(Into::into)
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &HmdMatrix34_t, b: &HmdMatrix34_t) {
+        for (a, b) in a.m.iter().flatten().zip(b.m.iter().flatten()) {
+            assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn rigid() {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        // 90 degrees around Y
+        let rotation = HmdQuaternionf_t {
+            w: half,
+            x: 0.0,
+            y: half,
+            z: 0.0,
+        };
+        let transform = from_translation_rotation(HmdVector3_t { v: [1.0, 2.0, 3.0] }, rotation);
+        let point = transform_point(&transform, HmdVector3_t { v: [1.0, 0.0, 0.0] });
+        let direction = transform_direction(&transform, HmdVector3_t { v: [1.0, 0.0, 0.0] });
+        for (actual, expected) in point.v.into_iter().zip([1.0, 2.0, 2.0]) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+        for (actual, expected) in direction.v.into_iter().zip([0.0, 0.0, -1.0]) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+
+        assert_close(
+            &multiply(&transform, &inverse_rigid(&transform)),
+            &identity(),
+        );
+        assert_close(&multiply(&identity(), &transform), &transform);
+        let rotation = super::rotation(&transform);
+        assert!((rotation.w - half).abs() < 1e-6 && (rotation.y - half).abs() < 1e-6);
+    }
+
+    #[cfg(any(feature = "mint", feature = "glam", feature = "nalgebra"))]
+    fn round_trip<T>(matrix: crate::HmdMatrix34)
+    where
+        T: From<crate::HmdMatrix34> + Into<crate::HmdMatrix34>,
+    {
+        let converted = T::from(matrix);
+        assert_eq!(converted.into(), matrix);
+    }
+
+    #[test]
+    #[cfg(any(feature = "mint", feature = "glam", feature = "nalgebra"))]
+    fn convert() {
+        let matrix = crate::HmdMatrix34::from(HmdMatrix34_t {
+            m: [
+                [1.0, 2.0, 3.0, 4.0],
+                [5.0, 6.0, 7.0, 8.0],
+                [9.0, 10.0, 11.0, 12.0],
+            ],
+        });
+        #[cfg(feature = "mint")]
+        round_trip::<mint::RowMatrix3x4<f32>>(matrix);
+        #[cfg(feature = "glam")]
+        {
+            round_trip::<glam::Affine3A>(matrix);
+            let affine = glam::Affine3A::from(matrix);
+            assert_eq!(affine.translation, glam::Vec3A::new(4.0, 8.0, 12.0));
+        }
+        #[cfg(feature = "nalgebra")]
+        {
+            round_trip::<nalgebra::Matrix3x4<f32>>(matrix);
+            assert_eq!(nalgebra::Matrix3x4::from(matrix)[(1, 2)], 7.0);
+        }
+    }
+}
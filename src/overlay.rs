@@ -21,6 +21,11 @@ impl<'a> VROverlay<'a> {
     pub(crate) fn new(table: &'a openvr_sys::VR_IVROverlay_FnTable) -> Self {
         Self { table }
     }
+
+    /// Gets the low-level wrapper of this interface for the functions not covered here.
+    pub fn low_level(self) -> crate::low_level::IVROverlay<'a> {
+        crate::low_level::IVROverlay::new(self.table)
+    }
 }
 
 impl<'a> VROverlay<'a> {
@@ -108,7 +113,7 @@ impl<'a> VROverlay<'a> {
                 &mut height,
             );
             if err != openvr_sys::EVROverlayError_VROverlayError_ArrayTooSmall {
-                return Err(crate::OverlayError::from_raw(err));
+                return Err(crate::OverlayError::from_ffi(err));
             }
             let mut data = Vec::<u8>::with_capacity((width * height * 4) as usize);
             let err = self.table.GetOverlayImageData.unwrap()(
@@ -130,7 +135,7 @@ impl<'a> VROverlay<'a> {
     pub fn get_overlay_error_name_from_enum(self, error: crate::OverlayError) -> &'a CStr {
         unsafe {
             CStr::from_ptr(self.table.GetOverlayErrorNameFromEnum.unwrap()(
-                error.as_raw(),
+                error.as_ffi(),
             ))
         }
     }
@@ -152,7 +157,7 @@ impl<'a> VROverlay<'a> {
         unsafe {
             mk_err(self.table.SetOverlayFlag.unwrap()(
                 handle,
-                flag.as_raw(),
+                flag.as_ffi(),
                 enabled,
             ))
         }
@@ -165,7 +170,7 @@ impl<'a> VROverlay<'a> {
     ) -> Result<bool> {
         unsafe {
             let mut enabled: bool = false;
-            let err = self.table.GetOverlayFlag.unwrap()(handle, flag.as_raw(), &mut enabled);
+            let err = self.table.GetOverlayFlag.unwrap()(handle, flag.as_ffi(), &mut enabled);
             mk_err(err)?;
             Ok(enabled)
         }
@@ -179,9 +184,7 @@ impl<'a> VROverlay<'a> {
             let mut result = 0;
             let err = self.table.GetOverlayFlags.unwrap()(handle, &mut result);
             mk_err(err)?;
-            Ok(crate::OverlayFlags::from_raw(
-                result as openvr_sys::VROverlayFlags,
-            ))
+            Ok(crate::OverlayFlags::from_raw(result))
         }
     }
 
@@ -312,7 +315,7 @@ impl<'a> VROverlay<'a> {
         unsafe {
             mk_err(self.table.SetOverlayTextureColorSpace.unwrap()(
                 handle,
-                color_space.as_raw(),
+                color_space.as_ffi(),
             ))
         }
     }
@@ -325,7 +328,7 @@ impl<'a> VROverlay<'a> {
             let mut color_space = 0;
             let err = self.table.GetOverlayTextureColorSpace.unwrap()(handle, &mut color_space);
             mk_err(err)?;
-            Ok(crate::ColorSpace::from_raw(color_space))
+            Ok(crate::ColorSpace::from_ffi(color_space))
         }
     }
 
@@ -362,7 +365,7 @@ impl<'a> VROverlay<'a> {
             let mut result = 0;
             let err = self.table.GetOverlayTransformType.unwrap()(handle, &mut result);
             mk_err(err)?;
-            Ok(crate::OverlayTransformType::from_raw(result))
+            Ok(crate::OverlayTransformType::from_ffi(result))
         }
     }
 
@@ -375,13 +378,23 @@ impl<'a> VROverlay<'a> {
         unsafe {
             let err = self.table.SetOverlayTransformAbsolute.unwrap()(
                 handle,
-                origin.as_raw(),
+                origin.as_ffi(),
                 as_mut_ptr(transform),
             );
             mk_err(err)
         }
     }
 
+    /// Typed version of [`set_overlay_transform_absolute`](Self::set_overlay_transform_absolute).
+    /// The origin is the universe of the transform.
+    pub fn set_overlay_transform_absolute_typed<U: crate::space::UniverseSpace>(
+        self,
+        handle: crate::VROverlayHandle_t,
+        transform: &crate::Transform<crate::space::Overlay, U>,
+    ) -> Result {
+        self.set_overlay_transform_absolute(handle, U::ORIGIN, transform.as_raw())
+    }
+
     pub fn get_overlay_transform_absolute(
         self,
         handle: crate::VROverlayHandle_t,
@@ -394,7 +407,7 @@ impl<'a> VROverlay<'a> {
                 &mut result.1,
             );
             mk_err(err)?;
-            Ok((crate::TrackingUniverseOrigin::from_raw(result.0), result.1))
+            Ok((crate::TrackingUniverseOrigin::from_ffi(result.0), result.1))
         }
     }
 
@@ -414,6 +427,16 @@ impl<'a> VROverlay<'a> {
         }
     }
 
+    /// Typed version of [`set_overlay_transform_tracked_device_relative`](Self::set_overlay_transform_tracked_device_relative)
+    pub fn set_overlay_transform_tracked_device_relative_typed(
+        self,
+        handle: crate::VROverlayHandle_t,
+        device: crate::TrackedDeviceIndex_t,
+        transform: &crate::Transform<crate::space::Overlay, crate::space::Device>,
+    ) -> Result {
+        self.set_overlay_transform_tracked_device_relative(handle, device, transform.as_raw())
+    }
+
     pub fn get_overlay_transform_tracked_device_relative(
         self,
         handle: crate::VROverlayHandle_t,
@@ -493,10 +516,10 @@ impl<'a> VROverlay<'a> {
         unsafe {
             mk_err(self.table.SetOverlayTransformProjection.unwrap()(
                 handle,
-                tracking_origin.as_raw(),
+                tracking_origin.as_ffi(),
                 as_mut_ptr(tracking_origin_to_overlay_transform),
                 as_mut_ptr(projection),
-                eye.as_raw(),
+                eye.as_ffi(),
             ))
         }
     }
@@ -523,7 +546,7 @@ impl<'a> VROverlay<'a> {
             let mut result = zeroed();
             let err = self.table.GetTransformForOverlayCoordinates.unwrap()(
                 handle,
-                origin.as_raw(),
+                origin.as_ffi(),
                 coordinates_in_overlay,
                 &mut result,
             );
@@ -563,7 +586,7 @@ impl<'a> VROverlay<'a> {
             let mut result = zeroed();
             let err = self.table.GetOverlayInputMethod.unwrap()(handle, &mut result);
             mk_err(err)?;
-            Ok(crate::OverlayInputMethod::from_raw(result))
+            Ok(crate::OverlayInputMethod::from_ffi(result))
         }
     }
 
@@ -575,7 +598,7 @@ impl<'a> VROverlay<'a> {
         unsafe {
             mk_err(self.table.SetOverlayInputMethod.unwrap()(
                 handle,
-                method.as_raw(),
+                method.as_ffi(),
             ))
         }
     }
@@ -826,11 +849,10 @@ impl<'a> VROverlay<'a> {
         user_value: u64,
     ) -> Result {
         unsafe {
-            #[allow(clippy::unnecessary_cast)] // KeyboardFlags can be signed
             mk_err(self.table.ShowKeyboard.unwrap()(
-                input_mode.as_raw(),
-                line_input_mode.as_raw(),
-                flags.as_raw() as u32,
+                input_mode.as_ffi(),
+                line_input_mode.as_ffi(),
+                flags.as_raw(),
                 description.as_ptr() as *mut _,
                 char_max,
                 existing_text.as_ptr() as *mut _,
@@ -851,12 +873,11 @@ impl<'a> VROverlay<'a> {
         user_value: u64,
     ) -> Result {
         unsafe {
-            #[allow(clippy::unnecessary_cast)] // KeyboardFlags can be signed
             mk_err(self.table.ShowKeyboardForOverlay.unwrap()(
                 handle,
-                input_mode.as_raw(),
-                line_input_mode.as_raw(),
-                flags.as_raw() as u32,
+                input_mode.as_ffi(),
+                line_input_mode.as_ffi(),
+                flags.as_raw(),
                 description.as_ptr() as *mut _,
                 char_max,
                 existing_text.as_ptr() as *mut _,
@@ -894,7 +915,7 @@ impl<'a> VROverlay<'a> {
     ) {
         unsafe {
             self.table.SetKeyboardTransformAbsolute.unwrap()(
-                tracking_origin.as_raw(),
+                tracking_origin.as_ffi(),
                 as_mut_ptr(transform),
             )
         }
@@ -926,7 +947,7 @@ impl<'a> VROverlay<'a> {
                 button2_text.map_or(null(), CStr::as_ptr) as _,
                 button3_text.map_or(null(), CStr::as_ptr) as _,
             );
-            Ok(crate::MessageOverlayResponse::from_raw(response))
+            Ok(crate::MessageOverlayResponse::from_ffi(response))
         }
     }
 
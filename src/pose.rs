@@ -0,0 +1,117 @@
+use crate::math::{from_translation_rotation, lerp, rotation, slerp, translation};
+use crate::{HmdMatrix34_t, HmdQuaternionf_t, HmdVector3_t, TrackingResult};
+
+/// The owned pose of tracked device.
+///
+/// This is created only for valid poses so `Option<DevicePose>` represents the validity.
+#[derive(Copy, Clone)]
+pub struct DevicePose {
+    pub device_to_absolute_tracking: HmdMatrix34_t,
+    /// The translation part of `device_to_absolute_tracking` in meters
+    pub position: HmdVector3_t,
+    /// The rotation part of `device_to_absolute_tracking`
+    pub rotation: HmdQuaternionf_t,
+    /// The velocity in meters per second
+    pub velocity: HmdVector3_t,
+    /// The angular velocity in radians per second
+    pub angular_velocity: HmdVector3_t,
+    pub tracking_result: TrackingResult,
+}
+
+impl DevicePose {
+    /// Returns `None` if the pose is not valid or the device is not connected.
+    pub fn from_raw(raw: &crate::TrackedDevicePose_t) -> Option<Self> {
+        if !raw.bPoseIsValid || !raw.bDeviceIsConnected {
+            return None;
+        }
+        let matrix = raw.mDeviceToAbsoluteTracking;
+        Some(Self {
+            device_to_absolute_tracking: matrix,
+            position: translation(&matrix),
+            rotation: rotation(&matrix),
+            velocity: raw.vVelocity,
+            angular_velocity: raw.vAngularVelocity,
+            tracking_result: TrackingResult::from_ffi(raw.eTrackingResult),
+        })
+    }
+
+    /// Interpolates poses with linear interpolation for position and velocities and
+    /// spherical linear interpolation for rotation. `t` is `0.0` for `self` and `1.0` for `other`.
+    pub fn interpolate(&self, other: &DevicePose, t: f32) -> DevicePose {
+        let position = lerp(self.position, other.position, t);
+        let rotation = slerp(self.rotation, other.rotation, t);
+        DevicePose {
+            device_to_absolute_tracking: from_translation_rotation(position, rotation),
+            position,
+            rotation,
+            velocity: lerp(self.velocity, other.velocity, t),
+            angular_velocity: lerp(self.angular_velocity, other.angular_velocity, t),
+            tracking_result: if t < 0.5 {
+                self.tracking_result
+            } else {
+                other.tracking_result
+            },
+        }
+    }
+
+    /// Returns `true` if the tracking result is `RunningOK`.
+    pub fn is_running_ok(&self) -> bool {
+        self.tracking_result == TrackingResult::RunningOK
+    }
+}
+
+impl std::fmt::Debug for DevicePose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let q = &self.rotation;
+        f.debug_struct("DevicePose")
+            .field("position", &self.position.v)
+            .field("rotation", &[q.w, q.x, q.y, q.z])
+            .field("velocity", &self.velocity.v)
+            .field("angular_velocity", &self.angular_velocity.v)
+            .field("tracking_result", &self.tracking_result)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw_pose(m: [[f32; 4]; 3]) -> crate::TrackedDevicePose_t {
+        let mut raw: crate::TrackedDevicePose_t = unsafe { std::mem::zeroed() };
+        raw.mDeviceToAbsoluteTracking = HmdMatrix34_t { m };
+        raw.eTrackingResult = TrackingResult::RunningOK.as_ffi();
+        raw.bPoseIsValid = true;
+        raw.bDeviceIsConnected = true;
+        raw
+    }
+
+    #[test]
+    fn decompose() {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        // 90 degrees around Y and 180 degrees around X
+        let cases = [
+            (
+                [[0., 0., 1., 1.], [0., 1., 0., 2.], [-1., 0., 0., 3.]],
+                [half, 0., half, 0.],
+            ),
+            (
+                [[1., 0., 0., 0.], [0., -1., 0., 0.], [0., 0., -1., 0.]],
+                [0., 1., 0., 0.],
+            ),
+        ];
+        for (m, expected) in cases {
+            let pose = DevicePose::from_raw(&raw_pose(m)).unwrap();
+            let q = pose.rotation;
+            for (actual, expected) in [q.w, q.x, q.y, q.z].into_iter().zip(expected) {
+                assert!((actual - expected).abs() < 1e-6, "{:?}", pose);
+            }
+            assert_eq!(pose.position.v, [m[0][3], m[1][3], m[2][3]]);
+            assert!(pose.is_running_ok());
+        }
+
+        let mut invalid = raw_pose(cases[0].0);
+        invalid.bPoseIsValid = false;
+        assert!(DevicePose::from_raw(&invalid).is_none());
+    }
+}
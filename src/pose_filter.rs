@@ -0,0 +1,378 @@
+use crate::math::{from_translation_rotation, lerp, rotation, slerp, translation};
+use crate::{
+    DevicePose, HmdMatrix34_t, HmdQuaternionf_t, HmdVector3_t, TrackedDeviceIndex,
+    TrackingUniverseOrigin, VROverlay, VROverlayHandle,
+};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// The kind and parameters of filter.
+///
+/// The distances are in meters for position and in radians for rotation.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum FilterKind {
+    /// Passes the value as is
+    #[default]
+    None,
+    /// Moves to the new value by `alpha` (`0.0..=1.0`) of the difference per sample.
+    Exponential { alpha: f32 },
+    /// The 1€ filter: smooths strongly while slow and follows quickly while fast.
+    ///
+    /// `min_cutoff` and `d_cutoff` are in Hz and `beta` is the coefficient of speed.
+    OneEuro {
+        min_cutoff: f32,
+        beta: f32,
+        d_cutoff: f32,
+    },
+    /// Ignores the movement smaller than `threshold`.
+    DeadZone { threshold: f32 },
+}
+
+impl FilterKind {
+    /// The 1€ filter with commonly used parameters
+    pub const ONE_EURO: FilterKind = FilterKind::OneEuro {
+        min_cutoff: 1.0,
+        beta: 0.5,
+        d_cutoff: 1.0,
+    };
+}
+
+mod internal {
+    pub trait FilterValueImpl: Copy {
+        fn distance(a: Self, b: Self) -> f32;
+        /// Moves from `a` to `b` by `t`
+        fn blend(a: Self, b: Self, t: f32) -> Self;
+    }
+}
+
+use internal::FilterValueImpl;
+
+/// The type can be filtered by [`Filter`]
+pub trait FilterValue: FilterValueImpl {}
+
+impl FilterValueImpl for HmdVector3_t {
+    fn distance(a: Self, b: Self) -> f32 {
+        (0..3)
+            .map(|i| (a.v[i] - b.v[i]) * (a.v[i] - b.v[i]))
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    fn blend(a: Self, b: Self, t: f32) -> Self {
+        lerp(a, b, t)
+    }
+}
+
+impl FilterValue for HmdVector3_t {}
+
+impl FilterValueImpl for HmdQuaternionf_t {
+    fn distance(a: Self, b: Self) -> f32 {
+        let dot = a.w * b.w + a.x * b.x + a.y * b.y + a.z * b.z;
+        2.0 * dot.abs().min(1.0).acos()
+    }
+
+    fn blend(a: Self, b: Self, t: f32) -> Self {
+        slerp(a, b, t)
+    }
+}
+
+impl FilterValue for HmdQuaternionf_t {}
+
+/// The filter of a value sequence
+#[derive(Copy, Clone)]
+pub struct Filter<T> {
+    kind: FilterKind,
+    last: Option<(T, Instant)>,
+    /// The filtered speed for one euro filter
+    speed: f32,
+}
+
+impl<T: FilterValue> Filter<T> {
+    pub fn new(kind: FilterKind) -> Self {
+        Self {
+            kind,
+            last: None,
+            speed: 0.0,
+        }
+    }
+
+    pub fn kind(&self) -> FilterKind {
+        self.kind
+    }
+
+    /// Changes the kind of filter. The state is reset.
+    pub fn set_kind(&mut self, kind: FilterKind) {
+        *self = Self::new(kind);
+    }
+
+    /// Forgets the previous values so that the next value is passed as is.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.speed = 0.0;
+    }
+
+    /// Filters the value sampled at the time.
+    pub fn filter(&mut self, value: T, time: Instant) -> T {
+        let (last, last_time) = match self.last {
+            Some(last) => last,
+            None => {
+                self.last = Some((value, time));
+                return value;
+            }
+        };
+        let result = match self.kind {
+            FilterKind::None => value,
+            FilterKind::Exponential { alpha } => T::blend(last, value, alpha.clamp(0.0, 1.0)),
+            FilterKind::OneEuro {
+                min_cutoff,
+                beta,
+                d_cutoff,
+            } => {
+                let dt = time.saturating_duration_since(last_time).as_secs_f32();
+                if dt <= 0.0 {
+                    return last;
+                }
+                let alpha = |cutoff: f32| {
+                    let tau = (2.0 * std::f32::consts::PI * cutoff).recip();
+                    (1.0 + tau / dt).recip()
+                };
+                let speed = T::distance(last, value) / dt;
+                self.speed += (speed - self.speed) * alpha(d_cutoff);
+                T::blend(last, value, alpha(min_cutoff + beta * self.speed))
+            }
+            FilterKind::DeadZone { threshold } => {
+                let distance = T::distance(last, value);
+                if distance <= threshold {
+                    last
+                } else {
+                    // follow to the edge of dead zone to avoid jumps
+                    T::blend(last, value, (distance - threshold) / distance)
+                }
+            }
+        };
+        self.last = Some((result, time));
+        result
+    }
+}
+
+impl<T> std::fmt::Debug for Filter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Filter")
+            .field("kind", &self.kind)
+            .field("initialized", &self.last.is_some())
+            .finish()
+    }
+}
+
+/// The filter kinds for position and rotation
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct PoseFilterConfig {
+    pub position: FilterKind,
+    pub rotation: FilterKind,
+}
+
+/// The filter of poses of a device, filtering position and rotation separately
+#[derive(Debug, Copy, Clone)]
+pub struct PoseFilter {
+    position: Filter<HmdVector3_t>,
+    rotation: Filter<HmdQuaternionf_t>,
+}
+
+impl PoseFilter {
+    pub fn new(config: PoseFilterConfig) -> Self {
+        Self {
+            position: Filter::new(config.position),
+            rotation: Filter::new(config.rotation),
+        }
+    }
+
+    pub fn config(&self) -> PoseFilterConfig {
+        PoseFilterConfig {
+            position: self.position.kind(),
+            rotation: self.rotation.kind(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.position.reset();
+        self.rotation.reset();
+    }
+
+    /// Filters the position and the rotation of the pose. Velocities are passed as is.
+    pub fn filter(&mut self, pose: &DevicePose, time: Instant) -> DevicePose {
+        let position = self.position.filter(pose.position, time);
+        let rotation = self.rotation.filter(pose.rotation, time);
+        DevicePose {
+            device_to_absolute_tracking: from_translation_rotation(position, rotation),
+            position,
+            rotation,
+            ..*pose
+        }
+    }
+
+    /// Filters the rigid transform.
+    pub fn filter_matrix(&mut self, matrix: &HmdMatrix34_t, time: Instant) -> HmdMatrix34_t {
+        let position = self.position.filter(translation(matrix), time);
+        let rotation = self.rotation.filter(rotation(matrix), time);
+        from_translation_rotation(position, rotation)
+    }
+}
+
+/// The pose filters per device.
+///
+/// The devices without specific config use the default config.
+#[derive(Debug, Clone, Default)]
+pub struct PoseFilters {
+    default_config: PoseFilterConfig,
+    configs: HashMap<TrackedDeviceIndex, PoseFilterConfig>,
+    filters: HashMap<TrackedDeviceIndex, PoseFilter>,
+}
+
+impl PoseFilters {
+    pub fn new(default_config: PoseFilterConfig) -> Self {
+        Self {
+            default_config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self, device_index: TrackedDeviceIndex) -> PoseFilterConfig {
+        self.configs
+            .get(&device_index)
+            .copied()
+            .unwrap_or(self.default_config)
+    }
+
+    /// Sets the config of the device. The state of the device is reset.
+    pub fn set_config(&mut self, device_index: TrackedDeviceIndex, config: PoseFilterConfig) {
+        self.configs.insert(device_index, config);
+        self.filters.remove(&device_index);
+    }
+
+    /// Resets the state of the device. Call this when the device is disconnected.
+    pub fn reset(&mut self, device_index: TrackedDeviceIndex) {
+        self.filters.remove(&device_index);
+    }
+
+    /// Resets the state when the device is activated or deactivated. Returns `true` if reset.
+    pub fn handle_event(&mut self, event: &crate::VREvent_t) -> bool {
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::TrackedDeviceActivated
+            | crate::EventType::TrackedDeviceDeactivated => {
+                self.reset(TrackedDeviceIndex(event.trackedDeviceIndex));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn filter(
+        &mut self,
+        device_index: TrackedDeviceIndex,
+        pose: &DevicePose,
+        time: Instant,
+    ) -> DevicePose {
+        let config = self.config(device_index);
+        self.filters
+            .entry(device_index)
+            .or_insert_with(|| PoseFilter::new(config))
+            .filter(pose, time)
+    }
+}
+
+/// The overlay positioned with `set_overlay_transform_absolute` through a [`PoseFilter`]
+#[derive(Debug, Copy, Clone)]
+pub struct FilteredOverlay {
+    handle: VROverlayHandle,
+    origin: TrackingUniverseOrigin,
+    filter: PoseFilter,
+}
+
+impl FilteredOverlay {
+    pub fn new(
+        handle: VROverlayHandle,
+        origin: TrackingUniverseOrigin,
+        config: PoseFilterConfig,
+    ) -> Self {
+        Self {
+            handle,
+            origin,
+            filter: PoseFilter::new(config),
+        }
+    }
+
+    pub fn handle(&self) -> VROverlayHandle {
+        self.handle
+    }
+
+    /// Filters the transform and sets it to the overlay.
+    pub fn set_transform_absolute(
+        &mut self,
+        overlay: VROverlay,
+        transform: &HmdMatrix34_t,
+    ) -> Result<(), crate::OverlayError> {
+        let filtered = self.filter.filter_matrix(transform, Instant::now());
+        overlay.set_overlay_transform_absolute(self.handle.0, self.origin, &filtered)
+    }
+
+    /// Makes the next transform to be set as is, e.g. after teleporting.
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn vector(x: f32) -> HmdVector3_t {
+        HmdVector3_t { v: [x, 0.0, 0.0] }
+    }
+
+    #[test]
+    fn filters() {
+        let start = Instant::now();
+        let at = |i: u64| start + Duration::from_millis(10 * i);
+        let run = |kind, values: &[f32]| {
+            let mut filter = Filter::new(kind);
+            (0..)
+                .zip(values)
+                .map(|(i, &x)| filter.filter(vector(x), at(i)).v[0])
+                .collect::<Vec<_>>()
+        };
+
+        let exponential = run(FilterKind::Exponential { alpha: 0.5 }, &[0.0, 1.0, 1.0]);
+        assert_eq!(exponential, vec![0.0, 0.5, 0.75]);
+
+        let dead_zone = run(
+            FilterKind::DeadZone { threshold: 0.1 },
+            &[0.0, 0.05, 0.3, 0.25],
+        );
+        for (actual, expected) in dead_zone.into_iter().zip([0.0, 0.0, 0.2, 0.2]) {
+            assert!((actual - expected).abs() < 1e-6);
+        }
+
+        // follows fast movement closer than slow movement
+        let slow = run(FilterKind::ONE_EURO, &[0.0, 0.001])[1];
+        let fast = run(FilterKind::ONE_EURO, &[0.0, 1.0])[1];
+        assert!(slow / 0.001 < fast / 1.0);
+        assert!(fast < 1.0);
+    }
+
+    #[test]
+    fn rotation() {
+        let mut filter = Filter::new(FilterKind::DeadZone { threshold: 0.1 });
+        let around_y = |angle: f32| HmdQuaternionf_t {
+            w: (angle / 2.0).cos(),
+            x: 0.0,
+            y: (angle / 2.0).sin(),
+            z: 0.0,
+        };
+        let now = Instant::now();
+        filter.filter(around_y(0.0), now);
+        assert_eq!(filter.filter(around_y(0.05), now).y, 0.0);
+        let moved = filter.filter(around_y(0.5), now);
+        assert!((HmdQuaternionf_t::distance(moved, around_y(0.4))) < 1e-4);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::math::{conjugate, multiply_quaternion};
+use crate::{
+    DevicePose, HmdQuaternionf_t, HmdVector3_t, TrackedDeviceIndex, TrackingUniverseOrigin,
+    VRSystem,
+};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone)]
+pub struct TimedPose {
+    pub time: Instant,
+    pub pose: DevicePose,
+}
+
+/// The history of poses per device in bounded ring buffers.
+///
+/// Call [`sample`](Self::sample) every frame or record poses from
+/// `poll_next_event_with_pose` with [`record_event`](Self::record_event).
+/// The poses recorded must be in one tracking universe.
+#[derive(Debug, Clone)]
+pub struct PoseHistory {
+    capacity: usize,
+    devices: HashMap<TrackedDeviceIndex, VecDeque<TimedPose>>,
+}
+
+impl PoseHistory {
+    /// Creates the history which keeps `capacity` poses per device. `0` records nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            devices: HashMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Records the current poses of all devices.
+    pub fn sample(&mut self, system: VRSystem, origin: TrackingUniverseOrigin) {
+        let now = Instant::now();
+        let poses = system.poses(origin, 0.0);
+        for (device_index, pose) in (0..).zip(poses) {
+            if let Some(pose) = pose {
+                self.record(TrackedDeviceIndex(device_index), now, pose);
+            }
+        }
+    }
+
+    /// Records the pose returned with the event by `poll_next_event_with_pose`.
+    /// The age of the event is taken into account.
+    pub fn record_event(&mut self, event: &crate::VREvent_t, pose: &crate::TrackedDevicePose_t) {
+        let device_index = TrackedDeviceIndex(event.trackedDeviceIndex);
+        if device_index.0 >= crate::consts::MAX_TRACKED_DEVICE_COUNT {
+            return;
+        }
+        // the event is ignored if the age is negative, NaN or too large for `Duration`
+        let age = match Duration::try_from_secs_f32(event.eventAgeSeconds) {
+            Ok(age) => age,
+            Err(_) => return,
+        };
+        if let Some(pose) = DevicePose::from_raw(pose) {
+            let now = Instant::now();
+            self.record(device_index, now.checked_sub(age).unwrap_or(now), pose);
+        }
+    }
+
+    /// Records the pose. The pose older than the latest pose of the device is ignored.
+    pub fn record(&mut self, device_index: TrackedDeviceIndex, time: Instant, pose: DevicePose) {
+        if self.capacity == 0 {
+            return;
+        }
+        let history = self.devices.entry(device_index).or_default();
+        if history.back().is_some_and(|x| x.time > time) {
+            return;
+        }
+        if history.len() >= self.capacity {
+            history.pop_front();
+        }
+        history.push_back(TimedPose { time, pose });
+    }
+
+    /// Removes the history of the device. Call this when the device is disconnected.
+    pub fn clear_device(&mut self, device_index: TrackedDeviceIndex) {
+        self.devices.remove(&device_index);
+    }
+
+    pub fn clear(&mut self) {
+        self.devices.clear();
+    }
+
+    /// Iterates the poses from oldest to newest.
+    pub fn iter(&self, device_index: TrackedDeviceIndex) -> impl Iterator<Item = &TimedPose> + '_ {
+        self.devices
+            .get(&device_index)
+            .into_iter()
+            .flat_map(|x| x.iter())
+    }
+
+    pub fn latest(&self, device_index: TrackedDeviceIndex) -> Option<&TimedPose> {
+        self.devices.get(&device_index)?.back()
+    }
+
+    /// Gets the pose at the time interpolated from the recorded poses.
+    /// Returns `None` if the time is out of the history.
+    pub fn at(&self, device_index: TrackedDeviceIndex, time: Instant) -> Option<DevicePose> {
+        let history = self.devices.get(&device_index)?;
+        let after = history.partition_point(|x| x.time < time);
+        let next = history.get(after)?;
+        if next.time == time {
+            return Some(next.pose);
+        }
+        let prev = history.get(after.checked_sub(1)?)?;
+        let t = (time - prev.time).as_secs_f32() / (next.time - prev.time).as_secs_f32();
+        Some(prev.pose.interpolate(&next.pose, t))
+    }
+
+    /// Gets the pose `duration` before now.
+    pub fn ago(&self, device_index: TrackedDeviceIndex, duration: Duration) -> Option<DevicePose> {
+        self.at(device_index, Instant::now().checked_sub(duration)?)
+    }
+
+    /// Estimates the velocity in meters per second from the latest pose and the pose `window` before it.
+    pub fn velocity(
+        &self,
+        device_index: TrackedDeviceIndex,
+        window: Duration,
+    ) -> Option<HmdVector3_t> {
+        let (from, to, seconds) = self.window(device_index, window)?;
+        Some(HmdVector3_t {
+            v: [0, 1, 2].map(|i| (to.position.v[i] - from.position.v[i]) / seconds),
+        })
+    }
+
+    /// Estimates the angular velocity in radians per second from the latest pose and the pose `window` before it.
+    pub fn angular_velocity(
+        &self,
+        device_index: TrackedDeviceIndex,
+        window: Duration,
+    ) -> Option<HmdVector3_t> {
+        let (from, to, seconds) = self.window(device_index, window)?;
+        let mut delta = multiply_quaternion(to.rotation, conjugate(from.rotation));
+        // take the shorter rotation
+        if delta.w < 0.0 {
+            delta = HmdQuaternionf_t {
+                w: -delta.w,
+                x: -delta.x,
+                y: -delta.y,
+                z: -delta.z,
+            };
+        }
+        let axis = [delta.x, delta.y, delta.z];
+        let sin = axis.iter().map(|x| x * x).sum::<f32>().sqrt();
+        // for small angle, angle / sin(angle / 2) ~= 2
+        let scale = if sin < 1e-6 {
+            2.0
+        } else {
+            2.0 * sin.atan2(delta.w) / sin
+        };
+        Some(HmdVector3_t {
+            v: axis.map(|x| x * scale / seconds),
+        })
+    }
+
+    fn window(
+        &self,
+        device_index: TrackedDeviceIndex,
+        window: Duration,
+    ) -> Option<(DevicePose, DevicePose, f32)> {
+        let latest = self.latest(device_index)?;
+        let oldest = self.devices.get(&device_index)?.front()?;
+        let from_time = latest.time.checked_sub(window)?.max(oldest.time);
+        let seconds = (latest.time - from_time).as_secs_f32();
+        if seconds <= 0.0 {
+            return None;
+        }
+        Some((self.at(device_index, from_time)?, latest.pose, seconds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::from_translation_rotation;
+
+    const DEVICE: TrackedDeviceIndex = TrackedDeviceIndex(1);
+
+    fn raw_pose(x: f32, angle: f32) -> crate::TrackedDevicePose_t {
+        let position = HmdVector3_t { v: [x, 0.0, 0.0] };
+        // around Y
+        let rotation = HmdQuaternionf_t {
+            w: (angle / 2.0).cos(),
+            x: 0.0,
+            y: (angle / 2.0).sin(),
+            z: 0.0,
+        };
+        let mut raw: crate::TrackedDevicePose_t = unsafe { std::mem::zeroed() };
+        raw.mDeviceToAbsoluteTracking = from_translation_rotation(position, rotation);
+        raw.eTrackingResult = crate::TrackingResult::RunningOK.as_ffi();
+        raw.bPoseIsValid = true;
+        raw.bDeviceIsConnected = true;
+        raw
+    }
+
+    fn pose(x: f32, angle: f32) -> DevicePose {
+        DevicePose::from_raw(&raw_pose(x, angle)).unwrap()
+    }
+
+    #[test]
+    fn interpolate() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut history = PoseHistory::new(3);
+        for i in 0..4 {
+            history.record(DEVICE, start + ms(100 * i), pose(i as f32, i as f32 * 0.5));
+        }
+        assert_eq!(history.iter(DEVICE).count(), 3);
+        assert!(history.at(DEVICE, start + ms(50)).is_none());
+        assert!(history.at(DEVICE, start + ms(400)).is_none());
+
+        let middle = history.at(DEVICE, start + ms(250)).unwrap();
+        assert!(close(middle.position.v[0], 2.5));
+        assert!(close(middle.rotation.y, (1.25f32 / 2.0).sin()));
+        let exact = history.at(DEVICE, start + ms(300)).unwrap();
+        assert!(close(exact.position.v[0], 3.0));
+
+        let velocity = history.velocity(DEVICE, ms(150)).unwrap();
+        assert!(close(velocity.v[0], 10.0));
+        let angular_velocity = history.angular_velocity(DEVICE, ms(1000)).unwrap();
+        assert!(close(angular_velocity.v[1], 5.0));
+        assert!(close(angular_velocity.v[0], 0.0));
+
+        let mut empty = PoseHistory::new(0);
+        empty.record(DEVICE, start, pose(0.0, 0.0));
+        assert!(empty.latest(DEVICE).is_none());
+    }
+
+    #[test]
+    fn event_age() {
+        let mut history = PoseHistory::new(3);
+        let mut event: crate::VREvent_t = unsafe { std::mem::zeroed() };
+        event.trackedDeviceIndex = DEVICE.0;
+        for age in [f32::INFINITY, f32::NAN, -1.0, f32::MAX] {
+            event.eventAgeSeconds = age;
+            history.record_event(&event, &raw_pose(0.0, 0.0));
+        }
+        assert!(history.latest(DEVICE).is_none());
+
+        event.eventAgeSeconds = 0.01;
+        history.record_event(&event, &raw_pose(0.0, 0.0));
+        assert!(history.latest(DEVICE).is_some());
+    }
+}
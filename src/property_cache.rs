@@ -0,0 +1,140 @@
+use crate::system::{Property, PropertyValue};
+use crate::{TrackedDeviceIndex, TrackedDeviceProperty, VRSystem};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// The number of cache hits and misses of [`PropertyCache`]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// The cache of tracked device properties.
+///
+/// Pass every event to [`handle_event`](Self::handle_event) to invalidate changed properties.
+/// Errors are not cached so the property not available yet will be queried again.
+#[derive(Default)]
+pub struct PropertyCache {
+    values: HashMap<(TrackedDeviceIndex, TrackedDeviceProperty), Box<dyn Any>>,
+    stats: CacheStats,
+}
+
+impl PropertyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the property from the cache, or from OpenVR if not cached.
+    pub fn get<T: PropertyValue + Clone + 'static>(
+        &mut self,
+        system: VRSystem,
+        device_index: TrackedDeviceIndex,
+        property: Property<T>,
+    ) -> Result<T, crate::TrackedPropertyError> {
+        let key = (device_index, property.prop());
+        if let Some(value) = self.values.get(&key).and_then(|x| x.downcast_ref::<T>()) {
+            self.stats.hits += 1;
+            return Ok(value.clone());
+        }
+        self.stats.misses += 1;
+        let value = system.get_property(device_index.0, property)?;
+        self.values.insert(key, Box::new(value.clone()));
+        Ok(value)
+    }
+
+    /// Invalidates properties changed by the event. Returns `true` if something is invalidated.
+    pub fn handle_event(&mut self, event: &crate::VREvent_t) -> bool {
+        let device_index = TrackedDeviceIndex(event.trackedDeviceIndex);
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::PropertyChanged => {
+                let prop = TrackedDeviceProperty::from_ffi(unsafe { event.data.property.prop });
+                if device_index == crate::consts::TRACKED_DEVICE_INDEX_INVALID {
+                    self.invalidate_property(prop)
+                } else {
+                    self.values.remove(&(device_index, prop)).is_some()
+                }
+            }
+            // another device may take the slot
+            crate::EventType::TrackedDeviceActivated
+            | crate::EventType::TrackedDeviceDeactivated
+            | crate::EventType::TrackedDeviceUpdated => self.invalidate_device(device_index),
+            _ => false,
+        }
+    }
+
+    pub fn invalidate_device(&mut self, device_index: TrackedDeviceIndex) -> bool {
+        let len = self.values.len();
+        self.values.retain(|&(index, _), _| index != device_index);
+        len != self.values.len()
+    }
+
+    pub fn invalidate_property(&mut self, prop: TrackedDeviceProperty) -> bool {
+        let len = self.values.len();
+        self.values.retain(|&(_, key), _| key != prop);
+        len != self.values.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+}
+
+impl std::fmt::Debug for PropertyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PropertyCache")
+            .field("len", &self.values.len())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE_1: TrackedDeviceIndex = TrackedDeviceIndex(1);
+    const DEVICE_2: TrackedDeviceIndex = TrackedDeviceIndex(2);
+
+    fn property_changed(
+        device_index: TrackedDeviceIndex,
+        prop: TrackedDeviceProperty,
+    ) -> crate::VREvent_t {
+        let mut event: crate::VREvent_t = unsafe { std::mem::zeroed() };
+        event.eventType = crate::EventType::PropertyChanged.as_raw();
+        event.trackedDeviceIndex = device_index.0;
+        event.data.property.prop = prop.as_ffi();
+        event
+    }
+
+    #[test]
+    fn invalidate() {
+        let mut cache = PropertyCache::new();
+        let serial = TrackedDeviceProperty::SerialNumberString;
+        let battery = TrackedDeviceProperty::DeviceBatteryPercentageFloat;
+        cache
+            .values
+            .insert((DEVICE_1, serial), Box::new(String::from("LHR-1")));
+        cache.values.insert((DEVICE_1, battery), Box::new(0.5f32));
+        cache.values.insert((DEVICE_2, battery), Box::new(0.8f32));
+
+        assert!(!cache.handle_event(&property_changed(TrackedDeviceIndex(3), battery)));
+        assert!(cache.handle_event(&property_changed(DEVICE_1, battery)));
+        assert!(cache.values.contains_key(&(DEVICE_1, serial)));
+        assert!(cache.values.contains_key(&(DEVICE_2, battery)));
+
+        let invalid = crate::consts::TRACKED_DEVICE_INDEX_INVALID;
+        assert!(cache.handle_event(&property_changed(invalid, battery)));
+        assert_eq!(cache.values.len(), 1);
+        assert!(cache.invalidate_device(DEVICE_1));
+        assert!(cache.values.is_empty());
+    }
+}
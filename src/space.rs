@@ -0,0 +1,157 @@
+//! Transforms tagged with their source and destination coordinate spaces.
+//!
+//! `Transform<Src, Dst>` converts coordinates in `Src` space into `Dst` space,
+//! so composing transforms of mismatched spaces is a compile error.
+
+use crate::math;
+use crate::{HmdMatrix34_t, HmdVector3_t, TrackingUniverseOrigin};
+use std::marker::PhantomData;
+
+/// The coordinate space
+pub trait Space: crate::Sealed {}
+
+/// The tracking universe, the space `set_overlay_transform_absolute` accepts
+pub trait UniverseSpace: Space {
+    const ORIGIN: TrackingUniverseOrigin;
+}
+
+macro_rules! spaces {
+    ($($(#[$meta: meta])* $name: ident $(= $origin: ident)?;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Copy, Clone)]
+            pub enum $name {}
+
+            impl crate::Sealed for $name {}
+            impl Space for $name {}
+            $(
+                impl UniverseSpace for $name {
+                    const ORIGIN: TrackingUniverseOrigin = TrackingUniverseOrigin::$origin;
+                }
+            )?
+        )*
+    };
+}
+
+spaces! {
+    /// The seated tracking universe
+    Seated = Seated;
+    /// The standing tracking universe
+    Standing = Standing;
+    /// The raw and uncalibrated tracking universe
+    RawAndUncalibrated = RawAndUncalibrated;
+    /// The space of a tracked device
+    Device;
+    /// The space of an overlay
+    Overlay;
+}
+
+/// The rigid transform from `Src` space to `Dst` space
+pub struct Transform<Src, Dst> {
+    matrix: HmdMatrix34_t,
+    _spaces: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<Src: Space, Dst: Space> Transform<Src, Dst> {
+    /// Tags the matrix with spaces. The caller is responsible for the spaces to be correct.
+    pub fn from_raw(matrix: HmdMatrix34_t) -> Self {
+        Self {
+            matrix,
+            _spaces: PhantomData,
+        }
+    }
+
+    pub fn as_raw(&self) -> &HmdMatrix34_t {
+        &self.matrix
+    }
+
+    pub fn into_raw(self) -> HmdMatrix34_t {
+        self.matrix
+    }
+
+    /// Applies `self` then `next`.
+    pub fn then<Next: Space>(&self, next: &Transform<Dst, Next>) -> Transform<Src, Next> {
+        Transform::from_raw(math::multiply(&next.matrix, &self.matrix))
+    }
+
+    /// The inverse transform. The matrix must be rigid.
+    pub fn inverse(&self) -> Transform<Dst, Src> {
+        Transform::from_raw(math::inverse_rigid(&self.matrix))
+    }
+
+    pub fn transform_point(&self, point: HmdVector3_t) -> HmdVector3_t {
+        math::transform_point(&self.matrix, point)
+    }
+
+    pub fn transform_direction(&self, direction: HmdVector3_t) -> HmdVector3_t {
+        math::transform_direction(&self.matrix, direction)
+    }
+}
+
+impl<S: Space> Transform<S, S> {
+    pub fn identity() -> Self {
+        Self::from_raw(math::identity())
+    }
+}
+
+impl<A: Space, B: Space, C: Space> std::ops::Mul<Transform<A, B>> for Transform<B, C> {
+    type Output = Transform<A, C>;
+
+    /// Composes like matrices: `rhs` is applied first.
+    fn mul(self, rhs: Transform<A, B>) -> Self::Output {
+        rhs.then(&self)
+    }
+}
+
+impl<Src, Dst> Copy for Transform<Src, Dst> {}
+
+impl<Src, Dst> Clone for Transform<Src, Dst> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Src, Dst> std::fmt::Debug for Transform<Src, Dst> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transform")
+            .field("from", &std::any::type_name::<Src>())
+            .field("to", &std::any::type_name::<Dst>())
+            .field("matrix", &self.matrix.m)
+            .finish()
+    }
+}
+
+impl crate::DevicePose {
+    /// The pose as a transform. `U` must be the universe the pose was got in.
+    pub fn transform<U: UniverseSpace>(&self) -> Transform<Device, U> {
+        Transform::from_raw(self.device_to_absolute_tracking)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose() {
+        let translate = |x: f32| {
+            let mut matrix = math::identity();
+            matrix.m[0][3] = x;
+            matrix
+        };
+        let seated_to_standing = Transform::<Seated, Standing>::from_raw(translate(1.0));
+        let device_to_seated = Transform::<Device, Seated>::from_raw(translate(2.0));
+        let device_to_standing = seated_to_standing * device_to_seated;
+        let origin = HmdVector3_t { v: [0.0; 3] };
+        assert_eq!(
+            device_to_standing.transform_point(origin).v,
+            [3.0, 0.0, 0.0]
+        );
+        let back = device_to_standing.then(&device_to_standing.inverse());
+        assert_eq!(
+            back.as_raw().m,
+            Transform::<Device, Device>::identity().as_raw().m
+        );
+        assert_eq!(Standing::ORIGIN, TrackingUniverseOrigin::Standing);
+    }
+}
@@ -5,6 +5,7 @@ pub use openvr_sys::HmdMatrix33_t;
 pub use openvr_sys::HmdMatrix34_t;
 pub use openvr_sys::HmdMatrix44_t;
 pub use openvr_sys::HmdQuad_t;
+pub use openvr_sys::HmdQuaternionf_t;
 pub use openvr_sys::HmdRect2_t;
 pub use openvr_sys::HmdVector2_t;
 pub use openvr_sys::HmdVector3_t;
@@ -44,8 +45,8 @@ impl From<OverlayTexture> for openvr_sys::Texture_t {
     fn from(t: OverlayTexture) -> Self {
         Self {
             handle: t.handle,
-            eType: t.tex_type.as_raw(),
-            eColorSpace: t.color_space.as_raw(),
+            eType: t.tex_type.as_ffi(),
+            eColorSpace: t.color_space.as_ffi(),
         }
     }
 }
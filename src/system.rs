@@ -1,4 +1,5 @@
 use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
 use std::mem::{size_of, zeroed};
 use std::os::raw::{c_char, c_ushort};
 use std::ptr::null_mut;
@@ -15,6 +16,11 @@ impl<'a> VRSystem<'a> {
     pub(crate) fn new(table: &'a openvr_sys::VR_IVRSystem_FnTable) -> Self {
         Self { table }
     }
+
+    /// Gets the low-level wrapper of this interface for the functions not covered here.
+    pub fn low_level(self) -> crate::low_level::IVRSystem<'a> {
+        crate::low_level::IVRSystem::new(self.table)
+    }
 }
 
 impl<'a> VRSystem<'a> {
@@ -31,14 +37,14 @@ impl<'a> VRSystem<'a> {
         near_z: f32,
         far_z: f32,
     ) -> crate::HmdMatrix44_t {
-        unsafe { self.table.GetProjectionMatrix.unwrap()(eye.as_raw(), near_z, far_z) }
+        unsafe { self.table.GetProjectionMatrix.unwrap()(eye.as_ffi(), near_z, far_z) }
     }
 
     pub fn get_projection_raw(self, eye: crate::Eye) -> RawProjection {
         let mut result: RawProjection = unsafe { zeroed() };
         unsafe {
             self.table.GetProjectionRaw.unwrap()(
-                eye.as_raw(),
+                eye.as_ffi(),
                 &mut result.left,
                 &mut result.right,
                 &mut result.top,
@@ -56,19 +62,19 @@ impl<'a> VRSystem<'a> {
     ) -> Option<crate::DistortionCoordinates_t> {
         let mut result: crate::DistortionCoordinates_t = unsafe { zeroed() };
         let success =
-            unsafe { self.table.ComputeDistortion.unwrap()(eye.as_raw(), u, v, &mut result) };
+            unsafe { self.table.ComputeDistortion.unwrap()(eye.as_ffi(), u, v, &mut result) };
         some_if!(result; if success)
     }
 
     pub fn get_eye_to_head_transform(self, eye: crate::Eye) -> crate::HmdMatrix34_t {
-        unsafe { self.table.GetEyeToHeadTransform.unwrap()(eye.as_raw()) }
+        unsafe { self.table.GetEyeToHeadTransform.unwrap()(eye.as_ffi()) }
     }
 
     pub fn get_time_since_last_vsync(self) -> Option<TimeSinceLastVsync> {
         let mut result: TimeSinceLastVsync = unsafe { zeroed() };
         let success = unsafe {
             self.table.GetTimeSinceLastVsync.unwrap()(
-                &mut result.second_since_last_vsync,
+                &mut result.seconds_since_last_vsync,
                 &mut result.frame_counter,
             )
         };
@@ -95,7 +101,7 @@ impl<'a> VRSystem<'a> {
         unsafe {
             self.table.GetOutputDevice.unwrap()(
                 &mut result,
-                texture_type.as_raw(),
+                texture_type.as_ffi(),
                 instance.as_pointer() as *mut openvr_sys::VkInstance_T,
             )
         };
@@ -119,7 +125,7 @@ impl<'a> VRSystem<'a> {
     ) {
         unsafe {
             self.table.GetDeviceToAbsoluteTrackingPose.unwrap()(
-                origin.as_raw(),
+                origin.as_ffi(),
                 predicted_seconds_to_phantoms_from_now,
                 tracked_device_poses.as_mut_ptr(),
                 tracked_device_poses
@@ -130,6 +136,22 @@ impl<'a> VRSystem<'a> {
         }
     }
 
+    /// Gets the poses of all device slots. The slots without valid pose are `None`.
+    pub fn poses(
+        self,
+        origin: crate::TrackingUniverseOrigin,
+        predicted_seconds_to_photons_from_now: f32,
+    ) -> [Option<crate::DevicePose>; crate::consts::MAX_TRACKED_DEVICE_COUNT as usize] {
+        let mut poses: [crate::TrackedDevicePose_t;
+            crate::consts::MAX_TRACKED_DEVICE_COUNT as usize] = unsafe { zeroed() };
+        self.get_device_to_absolute_tracking_pose(
+            origin,
+            predicted_seconds_to_photons_from_now,
+            &mut poses,
+        );
+        poses.map(|x| crate::DevicePose::from_raw(&x))
+    }
+
     pub fn get_seated_zero_pose_to_standing_absolute_tracking_pose(self) -> crate::HmdMatrix34_t {
         unsafe {
             self.table
@@ -146,6 +168,30 @@ impl<'a> VRSystem<'a> {
         }
     }
 
+    /// Typed version of [`get_seated_zero_pose_to_standing_absolute_tracking_pose`](Self::get_seated_zero_pose_to_standing_absolute_tracking_pose)
+    pub fn seated_to_standing(
+        self,
+    ) -> crate::Transform<crate::space::Seated, crate::space::Standing> {
+        crate::Transform::from_raw(self.get_seated_zero_pose_to_standing_absolute_tracking_pose())
+    }
+
+    /// Typed version of [`get_raw_zero_pose_to_standing_absolute_tracking_pose`](Self::get_raw_zero_pose_to_standing_absolute_tracking_pose)
+    pub fn raw_to_standing(
+        self,
+    ) -> crate::Transform<crate::space::RawAndUncalibrated, crate::space::Standing> {
+        crate::Transform::from_raw(self.get_raw_zero_pose_to_standing_absolute_tracking_pose())
+    }
+
+    /// Gets the transforms of all devices in the universe `U`. The slots without valid pose are `None`.
+    pub fn device_transforms<U: crate::space::UniverseSpace>(
+        self,
+        predicted_seconds_to_photons_from_now: f32,
+    ) -> [Option<crate::Transform<crate::space::Device, U>>;
+           crate::consts::MAX_TRACKED_DEVICE_COUNT as usize] {
+        self.poses(U::ORIGIN, predicted_seconds_to_photons_from_now)
+            .map(|x| x.map(|pose| pose.transform()))
+    }
+
     pub fn get_sorted_tracked_device_indices_of_class(
         self,
         tracked_device_class: crate::TrackedDeviceClass,
@@ -155,7 +201,7 @@ impl<'a> VRSystem<'a> {
         loop {
             unsafe {
                 let len = self.table.GetSortedTrackedDeviceIndicesOfClass.unwrap()(
-                    tracked_device_class.as_raw(),
+                    tracked_device_class.as_ffi(),
                     vec.as_mut_ptr(),
                     vec.capacity() as u32,
                     relative_to_tracked_device_index,
@@ -173,7 +219,7 @@ impl<'a> VRSystem<'a> {
         self,
         device_id: crate::TrackedDeviceIndex_t,
     ) -> crate::DeviceActivityLevel {
-        crate::DeviceActivityLevel::from_raw(unsafe {
+        crate::DeviceActivityLevel::from_ffi(unsafe {
             self.table.GetTrackedDeviceActivityLevel.unwrap()(device_id)
         })
     }
@@ -200,14 +246,14 @@ impl<'a> VRSystem<'a> {
         self,
         device_type: crate::TrackedControllerRole,
     ) -> crate::TrackedDeviceIndex_t {
-        unsafe { self.table.GetTrackedDeviceIndexForControllerRole.unwrap()(device_type.as_raw()) }
+        unsafe { self.table.GetTrackedDeviceIndexForControllerRole.unwrap()(device_type.as_ffi()) }
     }
 
     pub fn get_controller_role_for_tracked_device_index(
         self,
         device_index: crate::TrackedDeviceIndex_t,
     ) -> crate::TrackedControllerRole {
-        crate::TrackedControllerRole::from_raw(unsafe {
+        crate::TrackedControllerRole::from_ffi(unsafe {
             self.table.GetControllerRoleForTrackedDeviceIndex.unwrap()(device_index)
         })
     }
@@ -216,7 +262,7 @@ impl<'a> VRSystem<'a> {
         self,
         device_index: crate::TrackedDeviceIndex_t,
     ) -> crate::TrackedDeviceClass {
-        crate::TrackedDeviceClass::from_raw(unsafe {
+        crate::TrackedDeviceClass::from_ffi(unsafe {
             self.table.GetTrackedDeviceClass.unwrap()(device_index)
         })
     }
@@ -235,7 +281,7 @@ macro_rules! device_property {
         ) -> Result<$result, crate::TrackedPropertyError> {
             let mut err = unsafe { zeroed() };
             let result =
-                unsafe { self.table.$cfn_name.unwrap()(device_index, prop.as_raw(), &mut err) };
+                unsafe { self.table.$cfn_name.unwrap()(device_index, prop.as_ffi(), &mut err) };
             return_err!(err, crate::TrackedPropertyError, Success)?;
             Ok(result)
         }
@@ -274,28 +320,31 @@ impl<'a> VRSystem<'a> {
         device_index: crate::TrackedDeviceIndex_t,
         prop: crate::TrackedDeviceProperty,
     ) -> Result<Vec<T>, crate::TrackedPropertyError> {
-        let mut buffer = Vec::<T>::new();
+        // the buffer size and returned size are in bytes, not elements
+        let mut buffer = Vec::<u8>::new();
         loop {
             let mut err = 0;
-            unsafe {
-                let len = self.table.GetArrayTrackedDeviceProperty.unwrap()(
+            let len = unsafe {
+                self.table.GetArrayTrackedDeviceProperty.unwrap()(
                     device_index,
-                    prop.as_raw(),
+                    prop.as_ffi(),
                     T::get_type(),
-                    buffer.as_mut_ptr() as *mut std::os::raw::c_void,
+                    crate::slice_mut_ptr(&mut buffer) as *mut std::os::raw::c_void,
                     buffer.len() as u32,
                     &mut err,
-                );
-                if err == openvr_sys::ETrackedPropertyError_TrackedProp_Success {
-                    buffer.set_len(len as usize);
-                    return Ok(buffer);
-                } else if err == openvr_sys::ETrackedPropertyError_TrackedProp_BufferTooSmall {
-                    buffer.reserve(len as usize);
-                    continue;
-                }
-
-                return Err(crate::TrackedPropertyError::from_raw(err));
+                )
             };
+            if err == openvr_sys::ETrackedPropertyError_TrackedProp_Success {
+                buffer.truncate(len as usize);
+                return Ok(T::from_bytes(&buffer));
+            } else if err == openvr_sys::ETrackedPropertyError_TrackedProp_BufferTooSmall
+                && len as usize > buffer.len()
+            {
+                buffer.resize(len as usize, 0);
+                continue;
+            }
+
+            return Err(crate::TrackedPropertyError::from_ffi(err));
         }
     }
 
@@ -313,7 +362,7 @@ impl<'a> VRSystem<'a> {
             len = unsafe {
                 self.table.GetStringTrackedDeviceProperty.unwrap()(
                     device_index,
-                    prop.as_raw(),
+                    prop.as_ffi(),
                     buffer.as_mut_ptr() as *mut c_char,
                     buffer.len() as u32,
                     &mut err,
@@ -327,14 +376,35 @@ impl<'a> VRSystem<'a> {
                 continue;
             }
 
-            return Err(crate::TrackedPropertyError::from_raw(err));
+            return Err(crate::TrackedPropertyError::from_ffi(err));
         }
     }
 }
 
+impl<'a> VRSystem<'a> {
+    /// Gets the property with the type bound to the key, like `get_property(index, props::SERIAL_NUMBER)`
+    pub fn get_property<T: PropertyValue>(
+        self,
+        device_index: crate::TrackedDeviceIndex_t,
+        property: Property<T>,
+    ) -> Result<T, crate::TrackedPropertyError> {
+        T::get_property(self, device_index, property.prop())
+    }
+
+    /// Gets the property through the cache. See [`PropertyCache`](crate::PropertyCache).
+    pub fn get_property_cached<T: PropertyValue + Clone + 'static>(
+        self,
+        cache: &mut crate::PropertyCache,
+        device_index: crate::TrackedDeviceIndex_t,
+        property: Property<T>,
+    ) -> Result<T, crate::TrackedPropertyError> {
+        cache.get(self, crate::TrackedDeviceIndex(device_index), property)
+    }
+}
+
 impl<'a> VRSystem<'a> {
     pub fn get_prop_error_name_from_enum(self, error: crate::TrackedPropertyError) -> &'a CStr {
-        unsafe { CStr::from_ptr(self.table.GetPropErrorNameFromEnum.unwrap()(error.as_raw())) }
+        unsafe { CStr::from_ptr(self.table.GetPropErrorNameFromEnum.unwrap()(error.as_ffi())) }
     }
 
     pub fn poll_next_event(self) -> Option<crate::VREvent_t> {
@@ -356,7 +426,7 @@ impl<'a> VRSystem<'a> {
         let mut result_pose: crate::TrackedDevicePose_t = unsafe { zeroed() };
         let succeed = unsafe {
             self.table.PollNextEventWithPose.unwrap()(
-                origin.as_raw(),
+                origin.as_ffi(),
                 &mut result_event,
                 size_of::<crate::VREvent_t>() as u32,
                 as_mut_ptr(&mut result_pose),
@@ -366,7 +436,7 @@ impl<'a> VRSystem<'a> {
     }
 
     pub fn get_event_type_name_from_enum(self, event: crate::EventType) -> Option<&'a CStr> {
-        let ptr = unsafe { self.table.GetEventTypeNameFromEnum.unwrap()(event.as_raw()) };
+        let ptr = unsafe { self.table.GetEventTypeNameFromEnum.unwrap()(event.as_ffi()) };
         if ptr.is_null() {
             None
         } else {
@@ -379,7 +449,7 @@ impl<'a> VRSystem<'a> {
         eye: crate::Eye,
         type_: crate::HiddenAreaMeshType,
     ) -> crate::HiddenAreaMesh_t {
-        unsafe { self.table.GetHiddenAreaMesh.unwrap()(eye.as_raw(), type_.as_raw()) }
+        unsafe { self.table.GetHiddenAreaMesh.unwrap()(eye.as_ffi(), type_.as_ffi()) }
     }
 
     pub fn get_controller_state(
@@ -406,7 +476,7 @@ impl<'a> VRSystem<'a> {
         let mut result_pose: crate::TrackedDevicePose_t = unsafe { zeroed() };
         let success = unsafe {
             self.table.GetControllerStateWithPose.unwrap()(
-                origin.as_raw(),
+                origin.as_ffi(),
                 controller_device_index,
                 &mut result_state,
                 size_of::<crate::VRControllerState_t>() as u32,
@@ -431,10 +501,24 @@ impl<'a> VRSystem<'a> {
         };
     }
 
+    /// [`trigger_haptic_pulse`](Self::trigger_haptic_pulse) with `Duration`.
+    /// The duration is clamped to [`MAX_LEGACY_PULSE`](crate::haptics::MAX_LEGACY_PULSE)
+    /// since longer pulses are ignored by the runtime.
+    pub fn trigger_haptic_pulse_duration(
+        self,
+        controller_device_index: crate::TrackedDeviceIndex_t,
+        axis_id: u32,
+        duration: std::time::Duration,
+    ) {
+        let duration = duration.min(crate::haptics::MAX_LEGACY_PULSE);
+        let micros = duration.as_micros() as c_ushort;
+        self.trigger_haptic_pulse(controller_device_index, axis_id, micros)
+    }
+
     pub fn get_button_id_name_from_enum(self, button_id: crate::ButtonId) -> &'a CStr {
         unsafe {
             CStr::from_ptr(self.table.GetButtonIdNameFromEnum.unwrap()(
-                button_id.as_raw(),
+                button_id.as_ffi(),
             ))
         }
     }
@@ -445,7 +529,7 @@ impl<'a> VRSystem<'a> {
     ) -> &'a CStr {
         unsafe {
             CStr::from_ptr(self.table.GetControllerAxisTypeNameFromEnum.unwrap()(
-                axis_type.as_raw(),
+                axis_type.as_ffi(),
             ))
         }
     }
@@ -471,7 +555,11 @@ impl<'a> VRSystem<'a> {
         device_index: crate::TrackedDeviceIndex_t,
     ) -> Result<(), crate::FirmwareError> {
         let err = unsafe { self.table.PerformFirmwareUpdate.unwrap()(device_index) };
-        return_err!(err, crate::FirmwareError)
+        // both None and Success means no error
+        return_err!(err, crate::FirmwareError).or_else(|e| match e {
+            crate::FirmwareError::Success => Ok(()),
+            e => Err(e),
+        })
     }
 
     pub fn acknowledge_quit_exiting(self) {
@@ -505,29 +593,209 @@ impl<'a> VRSystem<'a> {
     }
 }
 
+/// The tangents of the half-angles of the eye frustum
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct RawProjection {
-    left: f32,
-    right: f32,
-    top: f32,
-    bottom: f32,
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TimeSinceLastVsync {
-    second_since_last_vsync: f32,
-    frame_counter: u64,
+    pub seconds_since_last_vsync: f32,
+    pub frame_counter: u64,
 }
 
 mod internal {
-    pub trait PropertyTypeImpl {
+    pub trait PropertyTypeImpl: Sized {
         fn get_type() -> openvr_sys::PropertyTypeTag_t;
+        /// converts bytes returned by `GetArrayTrackedDeviceProperty`
+        fn from_bytes(bytes: &[u8]) -> Vec<Self>;
+    }
+
+    pub trait PropertyValueImpl: Sized {
+        fn get_property(
+            system: super::VRSystem,
+            device_index: crate::TrackedDeviceIndex_t,
+            prop: crate::TrackedDeviceProperty,
+        ) -> Result<Self, crate::TrackedPropertyError>;
     }
 }
 
 use crate::as_mut_ptr;
-use internal::PropertyTypeImpl;
+use internal::{PropertyTypeImpl, PropertyValueImpl};
+
+/// The key of tracked device property with its value type.
+///
+/// The keys of known properties are in [`props`](crate::props).
+pub struct Property<T> {
+    prop: crate::TrackedDeviceProperty,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: PropertyValue> Property<T> {
+    /// Creates the key. If `T` is not the type of the property, getting it will fail.
+    pub const fn new(prop: crate::TrackedDeviceProperty) -> Self {
+        Self {
+            prop,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Property<T> {
+    pub const fn prop(self) -> crate::TrackedDeviceProperty {
+        self.prop
+    }
+}
+
+impl<T> Clone for Property<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Property<T> {}
+
+impl<T> std::fmt::Debug for Property<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Property").field(&self.prop).finish()
+    }
+}
+
+/// The key of tracked device property with any value type, used to list keys in [`props::ALL`](crate::props::ALL)
+#[derive(Debug, Copy, Clone)]
+pub enum AnyProperty {
+    Bool(Property<bool>),
+    Float(Property<f32>),
+    Int32(Property<i32>),
+    Uint64(Property<u64>),
+    String(Property<String>),
+    Matrix34(Property<crate::HmdMatrix34_t>),
+    Vector3(Property<crate::HmdVector3_t>),
+    FloatArray(Property<Vec<f32>>),
+    Int32Array(Property<Vec<i32>>),
+    Matrix34Array(Property<Vec<crate::HmdMatrix34_t>>),
+    Vector4Array(Property<Vec<crate::HmdVector4_t>>),
+}
+
+impl AnyProperty {
+    pub fn prop(self) -> crate::TrackedDeviceProperty {
+        match self {
+            AnyProperty::Bool(x) => x.prop(),
+            AnyProperty::Float(x) => x.prop(),
+            AnyProperty::Int32(x) => x.prop(),
+            AnyProperty::Uint64(x) => x.prop(),
+            AnyProperty::String(x) => x.prop(),
+            AnyProperty::Matrix34(x) => x.prop(),
+            AnyProperty::Vector3(x) => x.prop(),
+            AnyProperty::FloatArray(x) => x.prop(),
+            AnyProperty::Int32Array(x) => x.prop(),
+            AnyProperty::Matrix34Array(x) => x.prop(),
+            AnyProperty::Vector4Array(x) => x.prop(),
+        }
+    }
+}
+
+/// The type can be value of tracked device property
+pub trait PropertyValue: crate::Sealed + PropertyValueImpl {}
+
+macro_rules! property_value_impl {
+    ($ty: ty, $fn_name: ident) => {
+        impl PropertyValue for $ty {}
+        impl PropertyValueImpl for $ty {
+            #[inline(always)]
+            fn get_property(
+                system: VRSystem,
+                device_index: crate::TrackedDeviceIndex_t,
+                prop: crate::TrackedDeviceProperty,
+            ) -> Result<Self, crate::TrackedPropertyError> {
+                system.$fn_name(device_index, prop)
+            }
+        }
+    };
+}
+
+property_value_impl!(bool, get_bool_tracked_device_property);
+property_value_impl!(f32, get_float_tracked_device_property);
+property_value_impl!(i32, get_int32_tracked_device_property);
+property_value_impl!(u64, get_uint64_tracked_device_property);
+property_value_impl!(crate::HmdMatrix34_t, get_matrix34_tracked_device_property);
+
+impl PropertyValue for String {}
+impl PropertyValueImpl for String {
+    fn get_property(
+        system: VRSystem,
+        device_index: crate::TrackedDeviceIndex_t,
+        prop: crate::TrackedDeviceProperty,
+    ) -> Result<Self, crate::TrackedPropertyError> {
+        let value = system.get_string_tracked_device_property(device_index, prop)?;
+        Ok(value.to_string_lossy().into_owned())
+    }
+}
+impl crate::Sealed for String {}
+
+// there's no getter for single vector so use array getter
+impl PropertyValue for crate::HmdVector3_t {}
+impl PropertyValueImpl for crate::HmdVector3_t {
+    fn get_property(
+        system: VRSystem,
+        device_index: crate::TrackedDeviceIndex_t,
+        prop: crate::TrackedDeviceProperty,
+    ) -> Result<Self, crate::TrackedPropertyError> {
+        let mut value: Self = unsafe { zeroed() };
+        let mut err = unsafe { zeroed() };
+        unsafe {
+            system.table.GetArrayTrackedDeviceProperty.unwrap()(
+                device_index,
+                prop.as_ffi(),
+                Self::get_type(),
+                &mut value as *mut Self as *mut std::os::raw::c_void,
+                size_of::<Self>() as u32,
+                &mut err,
+            );
+        }
+        return_err!(err, crate::TrackedPropertyError, Success)?;
+        Ok(value)
+    }
+}
+
+impl<T: PropertyType> PropertyValue for Vec<T> {}
+impl<T: PropertyType> PropertyValueImpl for Vec<T> {
+    fn get_property(
+        system: VRSystem,
+        device_index: crate::TrackedDeviceIndex_t,
+        prop: crate::TrackedDeviceProperty,
+    ) -> Result<Self, crate::TrackedPropertyError> {
+        system.get_array_tracked_device_property(device_index, prop)
+    }
+}
+impl<T: PropertyType> crate::Sealed for Vec<T> {}
 
 pub trait PropertyType: crate::Sealed + PropertyTypeImpl {}
 
+/// reads plain old data elements from unaligned bytes. trailing incomplete element is ignored.
+///
+/// # Safety
+/// Any bit pattern must be valid for `T`.
+unsafe fn pod_from_bytes<T: Copy>(bytes: &[u8]) -> Vec<T> {
+    bytes
+        .chunks_exact(size_of::<T>())
+        .map(|chunk| std::ptr::read_unaligned(chunk.as_ptr() as *const T))
+        .collect()
+}
+
+/// splits nul-separated strings
+fn strings_from_bytes(bytes: &[u8]) -> impl Iterator<Item = std::borrow::Cow<'_, str>> {
+    let bytes = bytes.strip_suffix(b"\0").unwrap_or(bytes);
+    bytes
+        .split(|&b| b == 0)
+        .filter(move |_| !bytes.is_empty())
+        .map(String::from_utf8_lossy)
+}
+
 macro_rules! property_type_impl {
     ($ty: ty as $name: ident) => {
         impl PropertyType for $ty {}
@@ -536,6 +804,10 @@ macro_rules! property_type_impl {
             fn get_type() -> openvr_sys::PropertyTypeTag_t {
                 openvr_sys::$name as _
             }
+
+            fn from_bytes(bytes: &[u8]) -> Vec<Self> {
+                unsafe { pod_from_bytes(bytes) }
+            }
         }
         impl crate::Sealed for $ty {}
     };
@@ -544,35 +816,97 @@ macro_rules! property_type_impl {
 property_type_impl!(f32 as k_unFloatPropertyTag);
 property_type_impl!(i32 as k_unInt32PropertyTag);
 property_type_impl!(u64 as k_unUint64PropertyTag);
-property_type_impl!(bool as k_unBoolPropertyTag);
-//property_type_impl!(CStr as k_unStringPropertyTag);
-//impl PropertyType for &CStr {
-//    type CType = *const c_char;
-//
-//    fn get_type() -> openvr_sys::PropertyTypeTag_t {
-//        openvr_sys::k_unStringPropertyTag
-//    }
-//
-//    fn convert(c: Vec<CCType>) -> Vec<Self> {
-//        c.map(|x| unsafe { CStr::from_ptr(x) }).collect()
-//    }
-//}
-//impl crate::Sealed for &CStr {}
-//property_type_impl!(f32 as k_unErrorPropertyTag); // unknown // not exists for current openvr
-//property_type_impl!(f64 as k_unDoublePropertyTag); // not exists for current openvr
+property_type_impl!(f64 as k_unDoublePropertyTag);
+//property_type_impl!(f32 as k_unErrorPropertyTag); // unknown
 property_type_impl!(crate::HmdMatrix34_t as k_unHmdMatrix34PropertyTag);
 property_type_impl!(crate::HmdMatrix44_t as k_unHmdMatrix44PropertyTag);
 property_type_impl!(crate::HmdVector3_t as k_unHmdVector3PropertyTag);
 property_type_impl!(crate::HmdVector4_t as k_unHmdVector4PropertyTag);
 property_type_impl!(crate::HmdVector2_t as k_unHmdVector2PropertyTag);
 property_type_impl!(crate::HmdQuad_t as k_unHmdQuadPropertyTag);
-//property_type_impl!(crate::HiddenArea_t as k_unHiddenAreaPropertyTag); // maybe HiddenAreaMesh_t?
-//property_type_impl!(crate::PathHandleInfo_t as k_unPathHandleInfoTag); // maybe CStr?
-//property_type_impl!(crate::Action_t as k_unActionPropertyTag); // maybe VRActionHandle_t?
-//property_type_impl!(crate::InputValue_t as k_unInputValuePropertyTag); // maybe VRInputValueHandle_t?
-//property_type_impl!(crate::Wildcard_t as k_unWildcardPropertyTag); // unknown
-//property_type_impl!(crate::HapticVibration_t as k_unHapticVibrationPropertyTag); // maybe VREvent_HapticVibration_t?
-//property_type_impl!(crate::Skeleton_t as k_unSkeletonPropertyTag); // unknown
+property_type_impl!(HiddenAreaVertex as k_unHiddenAreaPropertyTag);
+property_type_impl!(crate::PathHandle as k_unPathHandleInfoTag);
+property_type_impl!(crate::VRActionHandle as k_unActionPropertyTag);
+property_type_impl!(crate::VRInputValueHandle as k_unInputValuePropertyTag);
+//property_type_impl!(crate::Wildcard_t as k_unWildcardPropertyTag); // matches any type so no value type
+property_type_impl!(openvr_sys::VREvent_HapticVibration_t as k_unHapticVibrationPropertyTag);
+property_type_impl!(crate::VRBoneTransform_t as k_unSkeletonPropertyTag);
 property_type_impl!(crate::SpatialAnchorPose_t as k_unSpatialAnchorPosePropertyTag);
-//property_type_impl!(crate::Json_t as k_unJsonPropertyTag); // unknown
 property_type_impl!(crate::VRActiveActionSet_t as k_unActiveActionSetPropertyTag);
+
+impl PropertyType for bool {}
+impl PropertyTypeImpl for bool {
+    #[inline(always)]
+    fn get_type() -> openvr_sys::PropertyTypeTag_t {
+        openvr_sys::k_unBoolPropertyTag as _
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Vec<Self> {
+        bytes.iter().map(|&b| b != 0).collect()
+    }
+}
+impl crate::Sealed for bool {}
+
+/// Strings are nul-separated in the array
+impl PropertyType for String {}
+impl PropertyTypeImpl for String {
+    #[inline(always)]
+    fn get_type() -> openvr_sys::PropertyTypeTag_t {
+        openvr_sys::k_unStringPropertyTag as _
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Vec<Self> {
+        strings_from_bytes(bytes).map(|x| x.into_owned()).collect()
+    }
+}
+
+/// JSON documents are nul-separated in the array. Invalid documents are `Value::Null`.
+#[cfg(feature = "serde_json")]
+impl PropertyType for serde_json::Value {}
+#[cfg(feature = "serde_json")]
+impl PropertyTypeImpl for serde_json::Value {
+    #[inline(always)]
+    fn get_type() -> openvr_sys::PropertyTypeTag_t {
+        openvr_sys::k_unJsonPropertyTag as _
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Vec<Self> {
+        strings_from_bytes(bytes)
+            .map(|x| serde_json::from_str(&x).unwrap_or(serde_json::Value::Null))
+            .collect()
+    }
+}
+#[cfg(feature = "serde_json")]
+impl crate::Sealed for serde_json::Value {}
+
+/// A vertex of hidden area mesh stored in `Prop_DisplayHiddenArea_Binary_Start` range of properties.
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct HiddenAreaVertex(pub crate::HmdVector2_t);
+
+#[cfg(test)]
+mod tests {
+    use super::PropertyTypeImpl;
+
+    #[test]
+    fn array_from_bytes() {
+        let bytes = [1.0f32, 2.5, -3.0]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect::<Vec<_>>();
+        // unaligned and incomplete trailing element
+        let mut unaligned = vec![0u8];
+        unaligned.extend_from_slice(&bytes);
+        unaligned.push(0);
+        assert_eq!(f32::from_bytes(&unaligned[1..]), vec![1.0, 2.5, -3.0]);
+        assert_eq!(bool::from_bytes(&[0, 1, 2]), vec![false, true, true]);
+    }
+
+    #[test]
+    fn strings_from_bytes() {
+        assert_eq!(String::from_bytes(b"foo\0bar\0"), vec!["foo", "bar"]);
+        assert_eq!(String::from_bytes(b"foo\0\0bar"), vec!["foo", "", "bar"]);
+        assert!(String::from_bytes(b"").is_empty());
+        assert!(String::from_bytes(b"\0").is_empty());
+    }
+}
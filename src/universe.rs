@@ -0,0 +1,288 @@
+use crate::math;
+use crate::space::{Overlay, RawAndUncalibrated, Seated, Space, Standing, UniverseSpace};
+use crate::{
+    DevicePose, HmdMatrix34_t, TrackingUniverseOrigin, Transform, VROverlay, VROverlayHandle,
+    VRSystem,
+};
+
+#[derive(Debug, Copy, Clone)]
+struct RegisteredOverlay {
+    handle: VROverlayHandle,
+    origin: TrackingUniverseOrigin,
+    /// The transform in raw universe which doesn't move on recenter
+    overlay_to_raw: Transform<Overlay, RawAndUncalibrated>,
+    dirty: bool,
+}
+
+/// Converts transforms and poses between tracking universes.
+///
+/// The transforms between universes are cached. Pass every event to
+/// [`handle_event`](Self::handle_event) to refresh them when the user recenters or the universe changes.
+///
+/// Overlays registered with [`set_overlay_transform`](Self::set_overlay_transform) are kept at the
+/// same physical place: `handle_event` re-applies the transforms converted for the new universe.
+/// The overlays failed to be re-applied are retried by [`update_overlays`](Self::update_overlays).
+#[derive(Debug, Clone)]
+pub struct UniverseConverter {
+    seated_to_standing: Transform<Seated, Standing>,
+    raw_to_standing: Transform<RawAndUncalibrated, Standing>,
+    overlays: Vec<RegisteredOverlay>,
+}
+
+impl UniverseConverter {
+    pub fn new(system: VRSystem) -> Self {
+        Self {
+            seated_to_standing: system.seated_to_standing(),
+            raw_to_standing: system.raw_to_standing(),
+            overlays: Vec::new(),
+        }
+    }
+
+    /// Reads the transforms between universes again.
+    /// Registered overlays are not re-applied until [`update_overlays`](Self::update_overlays) is called.
+    pub fn refresh(&mut self, system: VRSystem) {
+        self.set_transforms(system.seated_to_standing(), system.raw_to_standing());
+    }
+
+    fn set_transforms(
+        &mut self,
+        seated_to_standing: Transform<Seated, Standing>,
+        raw_to_standing: Transform<RawAndUncalibrated, Standing>,
+    ) {
+        self.seated_to_standing = seated_to_standing;
+        self.raw_to_standing = raw_to_standing;
+        for overlay in &mut self.overlays {
+            overlay.dirty = true;
+        }
+    }
+
+    /// Refreshes the transforms and re-applies registered overlays if the universe is changed
+    /// by the event. Returns `Ok(true)` if refreshed.
+    pub fn handle_event(
+        &mut self,
+        system: VRSystem,
+        overlay: VROverlay,
+        event: &crate::VREvent_t,
+    ) -> Result<bool, crate::OverlayError> {
+        match crate::EventType::from_raw(event.eventType) {
+            crate::EventType::SeatedZeroPoseReset
+            | crate::EventType::StandingZeroPoseReset
+            | crate::EventType::ChaperoneUniverseHasChanged
+            | crate::EventType::ChaperoneDataHasChanged => {
+                self.refresh(system);
+                self.update_overlays(overlay)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    pub fn seated_to_standing(&self) -> Transform<Seated, Standing> {
+        self.seated_to_standing
+    }
+
+    pub fn raw_to_standing(&self) -> Transform<RawAndUncalibrated, Standing> {
+        self.raw_to_standing
+    }
+
+    fn to_standing(&self, origin: TrackingUniverseOrigin) -> Option<HmdMatrix34_t> {
+        match origin {
+            TrackingUniverseOrigin::Seated => Some(*self.seated_to_standing.as_raw()),
+            TrackingUniverseOrigin::Standing => Some(math::identity()),
+            TrackingUniverseOrigin::RawAndUncalibrated => Some(*self.raw_to_standing.as_raw()),
+            _ => None,
+        }
+    }
+
+    /// The transform from universe `from` to universe `to`.
+    /// Returns `None` if the origin is not known by this version of OpenVR.
+    pub fn transform(
+        &self,
+        from: TrackingUniverseOrigin,
+        to: TrackingUniverseOrigin,
+    ) -> Option<HmdMatrix34_t> {
+        let from_to_standing = self.to_standing(from)?;
+        let to_to_standing = self.to_standing(to)?;
+        if from == to {
+            return Some(math::identity());
+        }
+        let from_standing = math::inverse_rigid(&to_to_standing);
+        Some(math::multiply(&from_standing, &from_to_standing))
+    }
+
+    /// Typed version of [`transform`](Self::transform)
+    pub fn transform_typed<Src: UniverseSpace, Dst: UniverseSpace>(&self) -> Transform<Src, Dst> {
+        let transform = self.transform(Src::ORIGIN, Dst::ORIGIN);
+        Transform::from_raw(transform.expect("origins of universe spaces are known"))
+    }
+
+    /// Converts the matrix in universe `from` into universe `to`.
+    /// Returns `None` if the origin is not known by this version of OpenVR.
+    pub fn convert(
+        &self,
+        matrix: &HmdMatrix34_t,
+        from: TrackingUniverseOrigin,
+        to: TrackingUniverseOrigin,
+    ) -> Option<HmdMatrix34_t> {
+        Some(math::multiply(&self.transform(from, to)?, matrix))
+    }
+
+    /// Typed version of [`convert`](Self::convert)
+    pub fn convert_typed<S: Space, Src: UniverseSpace, Dst: UniverseSpace>(
+        &self,
+        transform: &Transform<S, Src>,
+    ) -> Transform<S, Dst> {
+        transform.then(&self.transform_typed())
+    }
+
+    /// Converts the pose got in universe `from` into universe `to`.
+    /// Returns `None` if the origin is not known by this version of OpenVR.
+    pub fn convert_pose(
+        &self,
+        pose: &DevicePose,
+        from: TrackingUniverseOrigin,
+        to: TrackingUniverseOrigin,
+    ) -> Option<DevicePose> {
+        let transform = self.transform(from, to)?;
+        let device_to_absolute_tracking =
+            math::multiply(&transform, &pose.device_to_absolute_tracking);
+        Some(DevicePose {
+            device_to_absolute_tracking,
+            position: math::translation(&device_to_absolute_tracking),
+            rotation: math::rotation(&device_to_absolute_tracking),
+            velocity: math::transform_direction(&transform, pose.velocity),
+            angular_velocity: math::transform_direction(&transform, pose.angular_velocity),
+            tracking_result: pose.tracking_result,
+        })
+    }
+
+    /// Sets the absolute transform of the overlay and registers it to be re-applied after
+    /// the universe changes. Fails with `InvalidParameter` if the origin is not known.
+    pub fn set_overlay_transform(
+        &mut self,
+        overlay: VROverlay,
+        handle: VROverlayHandle,
+        origin: TrackingUniverseOrigin,
+        transform: &HmdMatrix34_t,
+    ) -> Result<(), crate::OverlayError> {
+        self.register_overlay(handle, origin, transform)?;
+        overlay.set_overlay_transform_absolute(handle.0, origin, transform)?;
+        self.overlays
+            .iter_mut()
+            .filter(|x| x.handle == handle)
+            .for_each(|x| x.dirty = false);
+        Ok(())
+    }
+
+    fn register_overlay(
+        &mut self,
+        handle: VROverlayHandle,
+        origin: TrackingUniverseOrigin,
+        transform: &HmdMatrix34_t,
+    ) -> Result<(), crate::OverlayError> {
+        let overlay_to_raw = self
+            .convert(
+                transform,
+                origin,
+                TrackingUniverseOrigin::RawAndUncalibrated,
+            )
+            .ok_or(crate::OverlayError::InvalidParameter)?;
+        let registered = RegisteredOverlay {
+            handle,
+            origin,
+            overlay_to_raw: Transform::from_raw(overlay_to_raw),
+            dirty: true,
+        };
+        match self.overlays.iter_mut().find(|x| x.handle == handle) {
+            Some(entry) => *entry = registered,
+            None => self.overlays.push(registered),
+        }
+        Ok(())
+    }
+
+    /// Stops re-applying the transform of the overlay.
+    pub fn unregister_overlay(&mut self, handle: VROverlayHandle) {
+        self.overlays.retain(|x| x.handle != handle);
+    }
+
+    /// Re-applies the transforms of registered overlays not applied since the universe changed.
+    ///
+    /// The overlays failed to be updated are retried on next call.
+    pub fn update_overlays(&mut self, overlay: VROverlay) -> Result<(), crate::OverlayError> {
+        let mut result = Ok(());
+        for index in 0..self.overlays.len() {
+            let registered = self.overlays[index];
+            if !registered.dirty {
+                continue;
+            }
+            let transform = self.overlay_transform(&registered);
+            match overlay.set_overlay_transform_absolute(
+                registered.handle.0,
+                registered.origin,
+                &transform,
+            ) {
+                Ok(()) => self.overlays[index].dirty = false,
+                Err(e) => result = result.and(Err(e)),
+            }
+        }
+        result
+    }
+
+    fn overlay_transform(&self, registered: &RegisteredOverlay) -> HmdMatrix34_t {
+        let transform = self.convert(
+            registered.overlay_to_raw.as_raw(),
+            TrackingUniverseOrigin::RawAndUncalibrated,
+            registered.origin,
+        );
+        transform.expect("origins of registered overlays are known")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(x: f32) -> HmdMatrix34_t {
+        let mut matrix = math::identity();
+        matrix.m[0][3] = x;
+        matrix
+    }
+
+    #[test]
+    fn convert() {
+        let mut converter = UniverseConverter {
+            seated_to_standing: Transform::from_raw(translate(1.0)),
+            raw_to_standing: Transform::from_raw(translate(10.0)),
+            overlays: Vec::new(),
+        };
+        let seated = TrackingUniverseOrigin::Seated;
+        let raw = TrackingUniverseOrigin::RawAndUncalibrated;
+        let converted = converter.convert(&translate(0.5), seated, raw).unwrap();
+        assert_eq!(converted.m, translate(-8.5).m);
+        let unknown = TrackingUniverseOrigin::from_raw(100);
+        assert!(converter.convert(&translate(0.5), unknown, raw).is_none());
+        assert!(converter.transform(unknown, unknown).is_none());
+        assert_eq!(
+            converter.register_overlay(VROverlayHandle(2), unknown, &translate(0.5)),
+            Err(crate::OverlayError::InvalidParameter)
+        );
+        assert!(converter.overlays.is_empty());
+        let typed: Transform<Seated, RawAndUncalibrated> = converter.transform_typed();
+        assert_eq!(typed.as_raw().m, translate(-9.0).m);
+
+        converter
+            .register_overlay(VROverlayHandle(1), seated, &translate(0.5))
+            .unwrap();
+        // recentered: seated zero moved by 2.0 in standing
+        converter.set_transforms(
+            Transform::from_raw(translate(3.0)),
+            Transform::from_raw(translate(10.0)),
+        );
+        let registered = converter.overlays[0];
+        assert!(registered.dirty);
+        assert_eq!(
+            converter.overlay_transform(&registered).m,
+            translate(-1.5).m
+        );
+    }
+}